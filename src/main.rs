@@ -1,15 +1,13 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use std::process::Command;
 use subxt::{OnlineClient, PolkadotConfig};
 use dotenv::dotenv;
 use std::env;
 use subxt::tx::PairSigner;
-use sp_core::{Pair, sr25519};
+use sp_core::{ecdsa, ed25519, Pair, sr25519};
 use subxt::utils::H256;
 use sp_core::Encode;
-use reqwest;
 use home::home_dir;
-use serde_json;
 use crate::custom_runtime::runtime_types::pallet_registration::types::NodeInfo;
 use std::convert::TryInto;
 use std::str::FromStr;
@@ -17,11 +15,19 @@ use subxt::config::substrate::MultiAddress;
 use crate::custom_runtime::registration::calls::types::force_register_node_with_hotkey::NodeType;
 use crate::custom_runtime::runtime_types::pallet_rankings::types::NodeRankings;
 use crate::custom_runtime::runtime_types::pallet_marketplace::types::FileInput;
+use crate::custom_runtime::runtime_types::pallet_marketplace::pallet::Call as MarketplaceCall;
+use crate::custom_runtime::runtime_types::pallet_balances::pallet::Call as BalancesCall;
+use crate::custom_runtime::runtime_types::pallet_staking::pallet::pallet::Call as StakingCall;
+use crate::custom_runtime::runtime_types::hippius_mainnet_runtime::RuntimeCall;
+use crate::custom_runtime::runtime_types::pallet_ipfs_pin::types::{PinRequest, StorageRequest};
 use crate::custom_runtime::runtime_types::pallet_credits::types::LockedCredit;
 use crate::custom_runtime::runtime_types::pallet_credits::types::LockPeriod;
 use crate::custom_runtime::runtime_types::pallet_marketplace::types::Plan;
+use crate::custom_runtime::runtime_types::pallet_marketplace::types::ImageDetails;
 use crate::custom_runtime::proxy::calls::types::add_proxy::ProxyType;
 use crate::custom_runtime::runtime_types::pallet_staking::RewardDestination::Staked;
+use crate::custom_runtime::runtime_types::pallet_staking::{ActiveEraInfo, Nominations, StakingLedger};
+use crate::custom_runtime::runtime_types::pallet_staking::slashing::SlashingSpans;
 use sp_core::crypto::Ss58Codec;
 use subxt::utils::AccountId32;
 use std::fs;
@@ -29,52 +35,363 @@ use std::path::Path;
 use codec::Decode;
 use subxt::dynamic;
 use csv::ReaderBuilder;
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use bip39::{Mnemonic, Language};
 use rand::Rng;
+use serde::Serialize;
 
 use tokio::fs::File;
-// use tokio_util::codec::{BytesCodec, FramedRead};
+use tokio_util::codec::{BytesCodec, FramedRead};
 use reqwest::multipart;
-use std::error::Error;
 use tokio::io::AsyncReadExt;
 use tokio::fs::read_dir;
 
 #[subxt::subxt(runtime_metadata_path = "metadata.scale")]
 pub mod custom_runtime {}
 
+/// Errors returned by command handlers. The categorised variants let callers tell
+/// failures apart; everything else carries its message in `Other`.
+#[derive(Debug, thiserror::Error)]
+enum CliError {
+    #[error("Keystore directory not found: {0} (use --keystore-path or set HIPPIUS_KEYSTORE_PATH)")]
+    KeystoreNotFound(String),
+    #[error("{0}")]
+    RpcConnect(String),
+    #[error("{0}")]
+    TxFinalization(String),
+    /// A finalized transaction whose dispatch failed; `pallet` names the
+    /// pallet of a module error
+    #[error("{message}")]
+    TxFailed { message: String, pallet: Option<String> },
+    #[error("invalid CID {cid:?}: {reason}")]
+    InvalidCid { cid: String, reason: String },
+    /// Arguments or configuration that cannot be acted on
+    #[error("{0}")]
+    Invalid(String),
+    #[error("{0}")]
+    DecodeFailed(String),
+    // Boxed: subxt's error is large and would bloat every `Result`
+    #[error(transparent)]
+    Subxt(Box<subxt::Error>),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl CliError {
+    /// Process exit code for this error: 1 generic, 2 usage/validation,
+    /// 3 network/RPC, 4 keystore, 5 transaction not finalized or failed.
+    fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Invalid(_) | CliError::InvalidCid { .. } => 2,
+            CliError::RpcConnect(_) | CliError::Http(_) => 3,
+            CliError::Subxt(e) if matches!(**e, subxt::Error::Rpc(_)) => 3,
+            CliError::KeystoreNotFound(_) => 4,
+            CliError::TxFinalization(_) | CliError::TxFailed { .. } => 5,
+            _ => 1,
+        }
+    }
+}
+
+impl From<subxt::Error> for CliError {
+    fn from(e: subxt::Error) -> Self {
+        CliError::Subxt(Box::new(e))
+    }
+}
+
+impl From<subxt::ext::subxt_core::Error> for CliError {
+    fn from(e: subxt::ext::subxt_core::Error) -> Self {
+        CliError::Subxt(Box::new(e.into()))
+    }
+}
+
+impl From<codec::Error> for CliError {
+    fn from(e: codec::Error) -> Self {
+        CliError::DecodeFailed(e.to_string())
+    }
+}
+
+impl From<subxt::error::DecodeError> for CliError {
+    fn from(e: subxt::error::DecodeError) -> Self {
+        CliError::DecodeFailed(e.to_string())
+    }
+}
+
+impl From<subxt::ext::scale_decode::visitor::DecodeError> for CliError {
+    fn from(e: subxt::ext::scale_decode::visitor::DecodeError) -> Self {
+        CliError::DecodeFailed(e.to_string())
+    }
+}
+
+impl From<hex::FromHexError> for CliError {
+    fn from(e: hex::FromHexError) -> Self {
+        CliError::DecodeFailed(e.to_string())
+    }
+}
+
+impl From<std::string::FromUtf8Error> for CliError {
+    fn from(e: std::string::FromUtf8Error) -> Self {
+        CliError::DecodeFailed(e.to_string())
+    }
+}
+
+impl From<bip39::Error> for CliError {
+    fn from(e: bip39::Error) -> Self {
+        CliError::Other(e.to_string())
+    }
+}
+
+impl From<std::time::SystemTimeError> for CliError {
+    fn from(e: std::time::SystemTimeError) -> Self {
+        CliError::Other(e.to_string())
+    }
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        CliError::Other(message)
+    }
+}
+
+impl From<&str> for CliError {
+    fn from(message: &str) -> Self {
+        CliError::Other(message.to_string())
+    }
+}
+
 /// A CLI for interacting with the Hippius Docker Registry and Substrate Chain
 #[derive(Parser)]
 #[command(name = "hippius-cli", about = "A CLI for managing Docker registries and interacting with a Substrate blockchain.")]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// Options shared by every subcommand
+    #[command(flatten)]
+    opts: GlobalOpts,
+
     /// The subcommand to run (e.g., "docker" or "create")
     #[command(subcommand)]
     command: Commands,
 }
 
+/// Options that apply to all commands
+#[derive(Args, Clone, Debug)]
+struct GlobalOpts {
+    /// Output format for command results
+    #[arg(long, short = 'o', global = true, value_enum, default_value_t = OutputFormat::Text, help = "Output format (text, json, json-pretty or csv)")]
+    output: OutputFormat,
+
+    /// Directory to write a JSON receipt to after every successful transaction
+    #[arg(long, global = true, value_name = "PATH", help = "Write a JSON receipt for each submitted transaction into this directory")]
+    receipt_dir: Option<String>,
+
+    /// Only print errors and command results
+    #[arg(long, short = 'q', global = true, conflicts_with = "verbose", help = "Only print errors and results; no progress or warnings")]
+    quiet: bool,
+
+    /// Also print debug detail such as extrinsic hashes and block numbers
+    #[arg(long, short = 'v', global = true, help = "Print debug detail (extrinsic hashes, block numbers, metadata cache use)")]
+    verbose: bool,
+
+    /// Answer yes to every confirmation prompt
+    #[arg(long, short = 'y', global = true, help = "Skip confirmation prompts for spending and destructive commands")]
+    yes: bool,
+
+    /// Build and print transactions without submitting them
+    #[arg(long, global = true, help = "Print the encoded and decoded call instead of submitting the transaction")]
+    dry_run: bool,
+
+    /// Print the estimated transaction fee before submitting
+    #[arg(long, global = true, help = "Print the estimated fee (TransactionPaymentApi partial fee) before submitting")]
+    estimate_fee: bool,
+
+    /// Print the encoded call and its blake2-256 hash before submitting
+    #[arg(long, global = true, help = "Print the call data and its blake2-256 hash (as referenced by multisig and governance) before submitting")]
+    show_call_hash: bool,
+
+    /// File holding the signer's mnemonic or secret URI
+    #[arg(long, global = true, value_name = "PATH", help = "Sign with the mnemonic, secret URI or raw 32-byte seed (e.g. seed.bin) in this file")]
+    seed_file: Option<String>,
+
+    /// Key that signs transactions and messages
+    #[arg(long, global = true, value_name = "SPEC", value_parser = parse_signer_spec, conflicts_with = "seed_file", help = "Signer to use: coldkey (the default lookup), hotkey:<name>, seed-file:<path> or mnemonic:<phrase>")]
+    signer: Option<SignerSpec>,
+
+    /// Allow the //Alice development account as the signer
+    #[arg(long, global = true, help = "Sign as //Alice when no other signer is configured (development chains only)")]
+    dev: bool,
+
+    /// Node keystore directory holding the HIPS key
+    #[arg(long, global = true, value_name = "PATH", help = "Node keystore directory (default: $HIPPIUS_KEYSTORE_PATH, else the mainnet/testnet node path)")]
+    keystore_path: Option<String>,
+
+    /// RPC endpoints to try in order; overrides SUBSTRATE_NODE_URL. The first one is
+    /// also the "local node" that `insert-key` and `get-node-id` talk to.
+    #[arg(long = "rpc-url", global = true, value_name = "URL", help = "RPC endpoint for every command (insert-key and get-node-id use the first); repeat to add failover endpoints")]
+    rpc_urls: Vec<String>,
+
+    /// Named profile from ~/.config/hippius/config.toml to take defaults from
+    #[arg(long, global = true, value_name = "NAME", help = "Config profile to use (default: default_profile from ~/.config/hippius/config.toml)")]
+    profile: Option<String>,
+
+    /// Settings of the active profile, filled in by `load_profile` after parsing
+    #[arg(skip)]
+    profile_settings: Profile,
+
+    /// Set while running the inner command of `proxy call`
+    #[arg(skip)]
+    proxy: Option<ProxiedSigner>,
+
+    /// Seconds to wait for the initial RPC connection before giving up
+    #[arg(long, global = true, default_value_t = 30, value_name = "SECS", help = "Seconds to wait for the RPC connection to be established")]
+    connect_timeout: u64,
+
+    /// Rounds of connection attempts over all endpoints, with exponential backoff in between
+    #[arg(long, global = true, default_value_t = 5, value_name = "N", help = "Connection attempts before giving up, backing off 1s, 2s, 4s... between them")]
+    connect_attempts: u32,
+
+    /// Download the runtime metadata even if a cached copy matches the runtime version
+    #[arg(long, global = true, help = "Ignore the on-disk metadata cache and download fresh metadata")]
+    no_cache: bool,
+
+    /// Seconds to wait for a submitted transaction to be finalized
+    #[arg(long, global = true, default_value_t = 120, value_name = "SECS", help = "Seconds to wait for a submitted transaction to be finalized")]
+    finalize_timeout: u64,
+
+    /// How far a submitted transaction must get before the command returns
+    #[arg(long, global = true, value_enum, default_value_t = WaitFor::Finalized, help = "Return once the transaction is in a block (inblock) or only once it is finalized")]
+    wait_for: WaitFor,
+
+    /// Tip paid to the block author on top of the fee
+    #[arg(long, global = true, value_name = "AMOUNT", help = "Tip (in the smallest unit) to prioritize transactions during congestion")]
+    tip: Option<u128>,
+
+    /// Nonce to sign with instead of the account's next one
+    #[arg(long, global = true, value_name = "N", help = "Sign with this nonce instead of fetching the account's next one")]
+    nonce: Option<u64>,
+
+    /// Blocks a transaction stays valid for
+    #[arg(long, global = true, value_name = "BLOCKS", value_parser = clap::value_parser!(u64).range(4..=65536), help = "Make transactions mortal: valid for about this many blocks from the latest one")]
+    mortality: Option<u64>,
+
+    /// Keep transactions valid indefinitely
+    #[arg(long, global = true, conflicts_with = "mortality", help = "Make transactions immortal (the default)")]
+    immortal: bool,
+
+    /// How many times to retry a submission that failed with an RPC error
+    #[arg(long, global = true, default_value_t = 0, value_name = "N", help = "Retry a submission this many times on transient RPC errors")]
+    retries: u32,
+}
+
+impl GlobalOpts {
+    /// Whether results should be emitted as JSON (compact or pretty)
+    fn is_json(&self) -> bool {
+        matches!(self.output, OutputFormat::Json | OutputFormat::JsonPretty)
+    }
+
+    /// The node keystore directory: `--keystore-path`, then HIPPIUS_KEYSTORE_PATH,
+    /// then the active profile, then the default node path for the network the
+    /// first RPC endpoint points at.
+    fn keystore_path(&self) -> String {
+        if let Some(path) = &self.keystore_path {
+            return path.clone();
+        }
+        if let Ok(path) = env::var("HIPPIUS_KEYSTORE_PATH") {
+            return path;
+        }
+        if let Some(path) = &self.profile_settings.keystore_path {
+            return path.clone();
+        }
+        let on_testnet = rpc_endpoints(self).first().is_some_and(|url| url.contains("testnet"));
+        if on_testnet { TESTNET_KEYSTORE_PATH } else { MAINNET_KEYSTORE_PATH }.to_string()
+    }
+
+    /// The secret URI transactions are signed with: `--seed-file`, then
+    /// SUBSTRATE_SEED_PHRASE, then the active profile's signer. `//Alice` is only
+    /// used with `--dev`; otherwise a missing signer is an error.
+    fn signer_suri(&self) -> Result<String, CliError> {
+        if let Some(path) = &self.seed_file {
+            return read_seed_file(path);
+        }
+        if let Some(suri) = env::var("SUBSTRATE_SEED_PHRASE").ok().or_else(|| self.profile_settings.signer.clone()) {
+            return Ok(suri);
+        }
+        if self.dev {
+            return Ok("//Alice".to_string());
+        }
+        Err(CliError::Invalid("no signer configured: pass --seed-file, set SUBSTRATE_SEED_PHRASE or a profile signer (or --dev to sign as //Alice on a development chain)".to_string()))
+    }
+
+    /// The `--signer` spec, defaulting to the usual coldkey lookup
+    fn signer_spec(&self) -> SignerSpec {
+        self.signer.clone().unwrap_or(SignerSpec::EnvColdkey)
+    }
+
+    /// Logs a progress line at info level; see `init_logging` for where it goes.
+    fn status(&self, message: impl std::fmt::Display) {
+        log::info!("{}", message);
+    }
+}
+
+/// `~/.config/hippius/config.toml`: named network profiles
+#[derive(serde::Deserialize, Default)]
+struct Config {
+    /// Profile used when `--profile` is not given
+    default_profile: Option<String>,
+    #[serde(default)]
+    profiles: std::collections::BTreeMap<String, Profile>,
+}
+
+/// Defaults for one network; flags and environment variables take precedence
+#[derive(serde::Deserialize, Clone, Debug, Default)]
+struct Profile {
+    rpc_url: Option<String>,
+    keystore_path: Option<String>,
+    /// Secret URI (mnemonic or `//Dev` path) used when SUBSTRATE_SEED_PHRASE is unset
+    signer: Option<String>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+enum OutputFormat {
+    /// Human-readable text
+    Text,
+    /// Machine-readable JSON on stdout
+    Json,
+    /// Indented JSON on stdout
+    JsonPretty,
+    /// Comma-separated rows with a header on stdout (list commands only)
+    Csv,
+}
+
+/// Stage of inclusion a submission waits for
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+enum WaitFor {
+    /// Included in a best block, which may still be retracted
+    #[value(name = "inblock")]
+    InBlock,
+    /// Finalized by GRANDPA
+    Finalized,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Storage operations for pinning and unpinning files
     Storage {
-        /// The storage operation to perform
-        #[arg(value_enum, help = "Specify the storage operation")]
-        storage_command: StorageCommand,
-
-        /// File hash and VM name
-        #[arg(help = "File hash and VM name")]
-        file_hash: String,
-
-        /// VM name
-        #[arg(help = "File name")]
-        file_name: String,
-
-        /// Optional miner IDs
-        #[arg(long = "miner-ids", help = "Optional miner IDs")]
-        miner_ids: Option<Vec<Vec<u8>>>,
+        #[command(subcommand)]
+        storage_command: StorageCommands,
     },
     /// List available OS disk images from the marketplace
-    ListImages,
+    ListImages {
+        /// Only show images whose OS name contains this substring
+        #[arg(long, value_name = "SUBSTRING", help = "Only show images whose OS name contains this substring")]
+        contains: Option<String>,
+    },
     /// Query free credits for signed-in account
     GetCredits,
     /// Insert a key
@@ -87,8 +404,20 @@ enum Commands {
         #[arg(help = "Specify the public key to insert")]
         public_key: String,
     },
-    /// Get information about your registered node
-    GetNodeInfo,
+    /// Get information about your registered node, another account's, or all nodes
+    GetNodeInfo {
+        /// Show the nodes registered to this account instead of the signer's
+        #[arg(long, value_name = "SS58_OR_LABEL", conflicts_with_all = ["all", "node_id"], help = "Show nodes registered to this account (SS58 address or wallet label) instead of the signer's")]
+        account: Option<String>,
+
+        /// Show every registered node
+        #[arg(long, conflicts_with = "node_id", help = "List every registered node")]
+        all: bool,
+
+        /// Look up a single node by its ID
+        #[arg(long, value_name = "ID", help = "Look up the node with this ID directly, without scanning the registry")]
+        node_id: Option<String>,
+    },
     /// Miner-related operations
     Miner {
         /// The miner operation to perform
@@ -102,8 +431,40 @@ enum Commands {
         node_type: CliNodeType,
 
         /// Node ID (typically a peer ID)
-        #[arg(long, help = "Node ID (e.g., libp2p peer ID)")]
+        #[arg(long, required_unless_present = "all", help = "Node ID (e.g., libp2p peer ID)")]
+        node_id: Option<String>,
+
+        /// Print the whole ranked list instead of a single node
+        #[arg(long, conflicts_with = "node_id", help = "Print every ranked node, sorted by rank")]
+        all: bool,
+
+        /// Report what the node's account actually earned since this block
+        #[arg(long, value_name = "NUMBER", requires = "node_id", help = "Also show the change of the node's free balance since this block (needs the node to still have that block's state)")]
+        since_block: Option<u32>,
+    },
+    /// Show the distributable reward pool and total ranked weight for a node type
+    RewardPool {
+        /// Type of the node whose ranking pallet to inspect
+        #[arg(long, help = "Type of node (Validator, ComputeMiner, StorageMiner)")]
+        node_type: CliNodeType,
+    },
+    /// Poll a miner's ranking and balance, printing what it earned since the start
+    WatchRewards {
+        /// Type of the miner
+        #[arg(long, help = "Type of miner (compute-miner or storage-miner)")]
+        node_type: CliNodeType,
+
+        /// Node ID of the miner
+        #[arg(long, help = "Node ID (e.g., libp2p peer ID) of the miner to watch")]
         node_id: String,
+
+        /// Seconds between two polls
+        #[arg(long, default_value_t = 60, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..), help = "Seconds between polls")]
+        interval: u64,
+
+        /// Poll once and exit
+        #[arg(long, help = "Print a single reading and exit instead of polling until Ctrl-C")]
+        once: bool,
     },
     /// Register a new node
     RegisterNodeWithColdkey {
@@ -113,57 +474,128 @@ enum Commands {
 
         /// Node ID (typically a peer ID)
         #[arg(long, help = "Node ID (e.g., libp2p peer ID)")]
-        node_id: String,
+        node_id: Option<String>,
 
         /// Optional flag to pay for registration in credits
         #[arg(long, help = "Pay for node registration using credits")]
         pay_in_credits: bool,
 
-        /// Optional IPFS Node ID (required for Miner nodes)
+        /// IPFS Node ID, required for Miner nodes
         #[arg(long, help = "IPFS Node ID (required for Miner nodes)")]
         ipfs_node_id: Option<String>,
+
+        /// Detect omitted node IDs from the local node and IPFS daemon
+        #[arg(long, help = "Fill in --node-id and --ipfs-node-id from the local node and IPFS daemon when omitted")]
+        auto_detect: bool,
     },
     /// Register a new node with a hotkey
     RegisterNodeWithHotkey {
-        /// The hotkey address
-        #[arg(help = "The hotkey address")]
+        /// The HIPS key (coldkey) account, as an SS58 address or wallet label
+        #[arg(help = "The HIPS key account (SS58 address or wallet label)")]
         hips_key: String,
-        /// The hotkey address
-        #[arg(help = "The hotkey address")]
+        /// The hotkey name or address
+        #[arg(help = "The hotkey name or address")]
         hotkey_address: String,
         /// Type of the node to register
         #[arg(long, help = "Type of node to register (Validator, ComputeMiner, StorageMiner)")]
         node_type: CliNodeType,
         /// Node ID (typically a peer ID)
         #[arg(long, help = "Node ID (e.g., libp2p peer ID)")]
-        node_id: String,
+        node_id: Option<String>,
         /// Optional flag to pay for registration in credits
         #[arg(long, help = "Pay for node registration using credits")]
         pay_in_credits: bool,
-        /// Optional IPFS Node ID (required for Miner nodes)
+        /// IPFS Node ID, required for Miner nodes
         #[arg(long, help = "IPFS Node ID (required for Miner nodes)")]
         ipfs_node_id: Option<String>,
+        /// Detect omitted node IDs from the local node and IPFS daemon
+        #[arg(long, help = "Fill in --node-id and --ipfs-node-id from the local node and IPFS daemon when omitted")]
+        auto_detect: bool,
+    },
+    /// Generate a new keypair for Substrate
+    GenerateKeys {
+        /// Directory to write the keypair files into
+        #[arg(long, help = "Directory to write public_key.ss58, mnemonic.txt and seed.bin into (default: ~/hippius/keystore)")]
+        out_dir: Option<String>,
+
+        /// Signature scheme of the keypair
+        #[arg(long, value_enum, default_value_t = KeyScheme::Sr25519, help = "Signature scheme of the generated keypair")]
+        scheme: KeyScheme,
+
+        /// Length of the generated mnemonic
+        #[arg(long, default_value_t = 12, value_parser = parse_word_count, help = "Number of mnemonic words: 12, 15, 18, 21 or 24")]
+        word_count: usize,
     },
-    /// Generate a new Sr25519 keypair for Substrate
-    GenerateKeys,
-    // /// Lock credits for a specific account
-    // LockCredits {
-    //     /// The amount of credits to lock
-    //     #[arg(help = "Specify the amount of credits to lock")]
-    //     amount: u128,
-    // },
+    // No lock-credits command: the Credits pallet has no user-callable lock call.
+    // Locks are recorded by the pallet's authorities (`fulfill_locked_credits`),
+    // so the CLI can only list them and show the lock period and minimum.
     /// List locked credits for the current account
     ListLockedCredits,
+    /// Mark a locked credit as fulfilled (Credits pallet authorities only)
+    FulfillLockedCredit {
+        /// The lock to fulfill, as shown by list-locked-credits
+        #[arg(help = "Lock ID as shown by list-locked-credits")]
+        lock_id: u64,
+
+        /// The account that owns the lock
+        #[arg(long, value_name = "SS58_OR_LABEL", help = "Account (SS58 address or wallet label) that owns the lock")]
+        account: String,
+
+        /// Hash of the transaction that settled the lock
+        #[arg(long, help = "Hash of the transaction that settled the lock, recorded on-chain with it")]
+        tx_hash: String,
+    },
+    /// Submit several operations from a JSON file as one atomic transaction
+    #[command(long_about = "Submit several operations from a JSON file as one atomic transaction.\n\nThe file holds an array of operations, each an object with an \"op\" field:\n  {\"op\": \"transfer\", \"to\": \"<ss58|label>\", \"amount\": 1000}\n  {\"op\": \"pin\", \"cid\": \"<cid>\", \"file_name\": \"<name>\", \"miner_ids\": [\"<id>\"]}\n  {\"op\": \"unpin\", \"cid\": \"<cid>\"}\n  {\"op\": \"stake\", \"amount\": \"1000000000000000000000\"}\n  {\"op\": \"unstake\", \"amount\": 1000}\n\nAmounts past 2^64 must be given as strings. They are sent in one utility.batch_all, so either all of them apply or none does.")]
+    Batch {
+        /// JSON file listing the operations
+        #[arg(long, value_name = "PATH", help = "JSON array of operations to submit together")]
+        file: String,
+    },
     /// Upload multiple files from a CSV file
     BulkUpload {
         /// Path to the CSV file containing file CIDs and names
         #[arg(short, long)]
         csv_path: String,
+
+        /// Drop rows with an invalid CID instead of aborting
+        #[arg(long, help = "Skip rows whose CID does not parse instead of aborting the upload")]
+        skip_invalid: bool,
+
+        /// Number of files pinned per transaction
+        #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..), help = "Files per storage_request transaction")]
+        batch_size: u32,
+
+        /// Number of miners to store rows without a miner_ids column on, picked by rank
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..), help = "Store files whose row names no miners on the N best-ranked active storage miners")]
+        replicas: Option<u32>,
     },
     /// List all available marketplace plans
     ListPlans,
+    /// Show a single marketplace plan
+    ShowPlan {
+        /// The plan's ID
+        #[arg(help = "Plan ID (0x-prefixed 32-byte hex, as shown by list-plans)")]
+        plan_id: H256,
+    },
     /// List all IPFS file storage requests for the current user
-    ListIpfsFiles,
+    ListIpfsFiles {
+        /// Compare the on-chain pins against a CSV manifest
+        #[arg(long, value_name = "CSV", help = "CSV manifest (file CID, file name) to compare the on-chain pins against")]
+        diff: Option<String>,
+
+        /// Show at most this many file hashes
+        #[arg(long, conflicts_with = "diff", help = "Show at most this many file hashes")]
+        limit: Option<usize>,
+
+        /// Skip this many file hashes before showing any
+        #[arg(long, default_value_t = 0, conflicts_with = "diff", help = "Skip this many file hashes before showing any")]
+        offset: usize,
+
+        /// Only show file hashes containing this substring
+        #[arg(long, value_name = "SUBSTRING", conflicts_with = "diff", help = "Only show file hashes whose CID contains this substring")]
+        grep: Option<String>,
+    },
     /// Fetch the current lock period from Credits pallet
     GetCurrentLockPeriod,
     /// Fetch the minimum lock amount from Credits pallet
@@ -173,16 +605,105 @@ enum Commands {
         #[command(subcommand)]
         account_command: AccountCommands,
     },
+    /// Purchase compute plans and manage the compute spaces they pay for
+    Compute {
+        #[command(subcommand)]
+        compute_command: ComputeCommands,
+    },
     /// Get the local peer ID
     GetNodeId,
     /// Get the IPFS Node ID
-    GetIpfsNodeId,
+    GetIpfsNodeId {
+        /// Base URL of the local IPFS HTTP API
+        #[arg(long, value_name = "URL", help = "IPFS HTTP API to query (default: $IPFS_API_URL, else http://127.0.0.1:5001)")]
+        ipfs_api: Option<String>,
+    },
     /// Get HIPS key by checking keystore files
     GetHipsKey,
     /// Create a new hotkey wallet
-    CreateHotkey,
+    CreateHotkey {
+        /// A friendly name for the hotkey
+        #[arg(long, help = "Name to store the hotkey under (default: its SS58 address)")]
+        name: Option<String>,
+
+        /// Encrypt the stored mnemonic with a passphrase
+        #[arg(long, help = "Prompt for a passphrase and store the mnemonic encrypted")]
+        encrypt: bool,
+
+        /// Length of the generated mnemonic
+        #[arg(long, default_value_t = 12, value_parser = parse_word_count, help = "Number of mnemonic words: 12, 15, 18, 21 or 24")]
+        word_count: usize,
+
+        /// Calls the hotkey may make for the signer
+        #[arg(long, value_enum, default_value_t = CliProxyType::NonTransfer, help = "Proxy type the hotkey is added with")]
+        proxy_type: CliProxyType,
+
+        /// Announcement delay of the proxy
+        #[arg(long, value_name = "BLOCKS", default_value_t = 0, help = "Blocks a proxied call must be announced before it can be executed")]
+        delay: u64,
+    },
+    /// Import an existing mnemonic as a hotkey wallet
+    ImportHotkey {
+        /// A friendly name for the hotkey
+        #[arg(long, help = "Name to store the hotkey under (default: its SS58 address)")]
+        name: Option<String>,
+
+        /// File holding the mnemonic
+        #[arg(long, value_name = "PATH", help = "Read the mnemonic from this file instead of stdin")]
+        mnemonic_file: Option<String>,
+
+        /// Encrypt the stored mnemonic with a passphrase
+        #[arg(long, help = "Prompt for a passphrase and store the mnemonic encrypted")]
+        encrypt: bool,
+
+        /// Add the hotkey as a proxy of the signer
+        #[arg(long, help = "Also add the hotkey as a proxy of the signer, like create-hotkey")]
+        register_proxy: bool,
+
+        /// Calls the hotkey may make for the signer
+        #[arg(long, value_enum, default_value_t = CliProxyType::NonTransfer, requires = "register_proxy", help = "Proxy type the hotkey is added with")]
+        proxy_type: CliProxyType,
+
+        /// Announcement delay of the proxy
+        #[arg(long, value_name = "BLOCKS", default_value_t = 0, requires = "register_proxy", help = "Blocks a proxied call must be announced before it can be executed")]
+        delay: u64,
+    },
+    /// Act on behalf of another account through a proxy
+    Proxy {
+        #[command(subcommand)]
+        proxy_command: ProxyCommands,
+    },
+    /// List the proxies an account has delegated to
+    ListProxies {
+        /// Account whose proxies to list
+        #[arg(long, value_name = "SS58_OR_LABEL", help = "List this account's proxies (SS58 address or wallet label) instead of the signer's")]
+        account: Option<String>,
+    },
+    /// Revoke a hotkey's proxy and delete it from the keystore
+    RemoveHotkey {
+        /// Hotkey to remove
+        #[arg(help = "Name or SS58 address of the hotkey")]
+        hotkey: String,
+    },
+    /// Print a hotkey's mnemonic after a typed confirmation
+    ExportHotkey {
+        /// Hotkey to export
+        #[arg(help = "Name or SS58 address of the hotkey")]
+        hotkey: String,
+
+        /// Allow --yes to skip the confirmation
+        #[arg(long, help = "Let --yes skip the typed confirmation (the mnemonic is printed in the clear)")]
+        i_understand_the_risk: bool,
+    },
     /// List all wallets
     ListWallets,
+    /// Check that every stored hotkey loads and matches its address
+    VerifyKeystore,
+    /// Back up and restore all hotkeys as a single JSON file
+    Wallet {
+        #[command(subcommand)]
+        wallet_command: WalletCommands,
+    },
     /// Swap the owner of a registered node
     SwapNodeOwner {
         /// The ID of the node to swap ownership
@@ -190,12 +711,8 @@ enum Commands {
         node_id: String,
 
         /// The new owner's account ID
-        #[arg(help = "The new owner's account ID")]
+        #[arg(help = "The new owner's account ID (SS58 address or wallet label)")]
         new_owner: String,
-
-        /// The account ID to sign the transaction
-        #[arg(help = "The account ID to sign the transaction")]
-        signer_account: String,
     },
     /// Upload a file to an IPFS node and print the CID
     UploadToIpfs {
@@ -203,14 +720,276 @@ enum Commands {
         #[arg(help = "Path to the file to upload to IPFS")]
         file_path: String,
     },
+    /// Print a shell completion script to stdout
+    #[command(visible_alias = "completions")]
+    Completion {
+        /// The shell to generate completions for
+        #[arg(value_enum, help = "Shell to generate completions for (bash, zsh, fish, powershell, elvish)")]
+        shell: clap_complete::Shell,
+    },
+    /// Inspect the CLI configuration
+    Config {
+        #[command(subcommand)]
+        config_command: ConfigCommands,
+    },
+    /// Check the local setup for common problems
+    Doctor {
+        /// Offer to fix each problem that can be fixed locally
+        #[arg(long, help = "Attempt safe local fixes, asking before each one")]
+        fix: bool,
+    },
+    /// Show the signer, RPC endpoint, network and coldkey commands would use
+    Whoami,
+    /// Inspect the RPC node commands connect to
+    Node {
+        #[command(subcommand)]
+        node_command: NodeCommands,
+    },
+    /// Print events from finalized blocks as they arrive, until Ctrl-C
+    WatchEvents {
+        /// Only show events of this pallet
+        #[arg(long, help = "Only show events of this pallet (e.g. Marketplace), case-insensitive")]
+        pallet: Option<String>,
+
+        /// Only show events that mention the signer's account
+        #[arg(long, help = "Only show events whose fields contain the signer's account")]
+        mine: bool,
+
+        /// Replay finalized blocks from this number before following new ones
+        #[arg(long, value_name = "NUMBER", help = "Replay finalized blocks starting at this block number first")]
+        from_block: Option<u32>,
+    },
+    /// Sign a message with the signer or a local hotkey
+    SignMessage {
+        /// The message to sign; read from stdin when omitted or `-`
+        #[arg(help = "Message to sign; read verbatim from stdin when omitted or '-'")]
+        message: Option<String>,
+
+        /// Sign with this local hotkey instead of the signer
+        #[arg(long, value_name = "NAME_OR_ADDRESS", help = "Sign with a hotkey from the keystore (name or address) instead of the signer")]
+        hotkey: Option<String>,
+    },
+    /// Verify an sr25519 message signature
+    VerifyMessage {
+        /// The signed message, or `-` to read it from stdin
+        #[arg(help = "Signed message, or '-' to read it verbatim from stdin")]
+        message: String,
+
+        /// Hex-encoded signature
+        #[arg(help = "Hex-encoded 64-byte sr25519 signature")]
+        signature: String,
+
+        /// The account that signed
+        #[arg(help = "Signer's SS58 address or wallet label")]
+        account: String,
+    },
+    /// Decode hex-encoded call data or a full extrinsic into a readable call
+    DecodeCall {
+        /// The SCALE-encoded call or extrinsic
+        #[arg(help = "Hex-encoded call data or signed/unsigned extrinsic, with or without 0x")]
+        hex: String,
+    },
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
-enum StorageCommand {
+#[derive(Subcommand)]
+enum StorageCommands {
     /// Pin files to storage
-    Pin,
+    Pin {
+        /// File hash and VM name
+        #[arg(help = "File hash and VM name")]
+        file_hash: String,
+
+        /// VM name
+        #[arg(help = "File name")]
+        file_name: String,
+
+        /// Optional miner IDs
+        #[arg(long = "miner-ids", value_name = "ID", value_delimiter = ',', help = "Miners to store the file on, comma separated or repeated (e.g. --miner-ids miner1,miner2)")]
+        miner_ids: Option<Vec<String>>,
+
+        /// Number of miners to store the file on, picked by rank
+        #[arg(long, value_name = "N", conflicts_with = "miner_ids", value_parser = clap::value_parser!(u32).range(1..), help = "Store the file on the N best-ranked active storage miners")]
+        replicas: Option<u32>,
+    },
+    /// Upload a local file to IPFS and pin the resulting CID
+    PinFile {
+        /// Path of the file to upload
+        #[arg(help = "Local file to upload and pin")]
+        path: String,
+
+        /// Base URL of the IPFS HTTP API to upload through
+        #[arg(long, value_name = "URL", help = "IPFS HTTP API to add the file through (default: $IPFS_API_URL, else the Hippius relay)")]
+        ipfs_api: Option<String>,
+
+        /// Optional miner IDs
+        #[arg(long = "miner-ids", value_name = "ID", value_delimiter = ',', help = "Miners to store the file on, comma separated or repeated (e.g. --miner-ids miner1,miner2)")]
+        miner_ids: Option<Vec<String>>,
+
+        /// Number of miners to store the file on, picked by rank
+        #[arg(long, value_name = "N", conflicts_with = "miner_ids", value_parser = clap::value_parser!(u32).range(1..), help = "Store the file on the N best-ranked active storage miners")]
+        replicas: Option<u32>,
+    },
     /// Unpin a specific file
-    Unpin,
+    Unpin {
+        /// The CID of the file to unpin
+        #[arg(help = "File hash to unpin")]
+        file_hash: String,
+    },
+    /// Unpin every CID listed in a file, in one batched transaction
+    UnpinBulk {
+        /// Path to a single-column CSV (or newline-delimited file) of CIDs
+        #[arg(short, long, help = "Single-column CSV or newline-delimited file of CIDs to unpin")]
+        csv_path: String,
+    },
+    /// Show the pinning and replication state of a file
+    Status {
+        /// The CID of the file to inspect
+        #[arg(help = "File hash (CID) to look up")]
+        cid: String,
+
+        /// Look up this account's storage request instead of the signer's
+        #[arg(long, value_name = "SS58_OR_LABEL", help = "Owner of the storage request (SS58 address or wallet label) instead of the signer; needs no signer")]
+        account: Option<String>,
+    },
+    /// Block until a file is stored by enough miners, or time out
+    Wait {
+        /// The CID of the file to wait for
+        #[arg(help = "File hash (CID) to wait for")]
+        cid: String,
+
+        /// Give up after this many seconds
+        #[arg(long, default_value_t = 300, value_name = "SECONDS", help = "Fail if the file is not stored within this many seconds")]
+        timeout: u64,
+
+        /// Number of pinned replicas to wait for
+        #[arg(long, value_name = "N", help = "Replicas to wait for (default: the replicas requested)")]
+        replicas: Option<u32>,
+
+        /// Seconds between two checks
+        #[arg(long, default_value_t = 6, value_name = "SECONDS", help = "Seconds between checks")]
+        interval: u64,
+
+        /// Wait for this account's storage request instead of the signer's
+        #[arg(long, value_name = "SS58_OR_LABEL", help = "Owner of the storage request (SS58 address or wallet label) instead of the signer; needs no signer")]
+        account: Option<String>,
+    },
+    /// Estimate what storing a file costs at the marketplace's current price
+    CostEstimate {
+        /// The CID of the file, whose size is asked from IPFS
+        #[arg(required_unless_present = "size_bytes", conflicts_with = "size_bytes", help = "File hash (CID) to price; its size is read from the IPFS API")]
+        cid: Option<String>,
+
+        /// Size of the file, instead of a CID
+        #[arg(long, value_name = "BYTES", help = "Price a file of this many bytes")]
+        size_bytes: Option<u64>,
+
+        /// Number of billing periods to add up
+        #[arg(long, default_value_t = 1, value_name = "N", help = "Billing periods (eras) to total the cost over")]
+        periods: u64,
+
+        /// Base URL of the IPFS HTTP API to read the size from
+        #[arg(long, value_name = "URL", help = "IPFS HTTP API to stat the CID through (default: $IPFS_API_URL, else the Hippius relay)")]
+        ipfs_api: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ComputeCommands {
+    /// Request a boot of the compute space bought with a plan
+    Boot {
+        #[arg(long, help = "Plan ID the compute space was purchased with")]
+        plan_id: H256,
+    },
+    /// Request a reboot of the compute space bought with a plan
+    Reboot {
+        #[arg(long, help = "Plan ID the compute space was purchased with")]
+        plan_id: H256,
+    },
+    /// Request a stop of the compute space bought with a plan
+    Stop {
+        #[arg(long, help = "Plan ID the compute space was purchased with")]
+        plan_id: H256,
+    },
+    /// Request deletion of the compute space bought with a plan
+    Delete {
+        #[arg(long, help = "Plan ID the compute space was purchased with")]
+        plan_id: H256,
+    },
+    /// Purchase a compute plan from the marketplace
+    Purchase(ComputePurchase),
+    /// List the compute instances bought by the signer
+    ListInstances,
+}
+
+#[derive(Subcommand)]
+enum ProxyCommands {
+    /// Run a transaction command as a proxy: its call is wrapped in
+    /// `proxy.proxy` and signed with a hotkey
+    Call {
+        /// Account the hotkey is a proxy of
+        #[arg(long, value_name = "SS58_OR_LABEL", help = "Account to act for (SS58 address or wallet label); the hotkey must be its proxy")]
+        real: String,
+
+        /// Hotkey that signs the wrapped call
+        #[arg(long, help = "Name or SS58 address of the hotkey to sign with")]
+        hotkey: String,
+
+        /// The command whose transaction is proxied, with its arguments
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true, value_name = "COMMAND", help = "Command to run as the proxy, e.g. `compute stop --plan-id 0x...` (global options go before `proxy call`)")]
+        command: Vec<String>,
+    },
+}
+
+/// The command line after `proxy call --real .. --hotkey ..`, parsed on its
+/// own since a `Commands` field inside `Commands` would make the tree infinite
+#[derive(Parser)]
+#[command(name = "hipc proxy call")]
+struct ProxiedCommand {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+/// Set by `proxy call`: transactions are wrapped in `proxy.proxy` for `real`
+/// and signed with the local `hotkey` instead of the configured signer.
+#[derive(Clone, Debug)]
+struct ProxiedSigner {
+    real: AccountId32,
+    hotkey: String,
+}
+
+#[derive(Args)]
+struct ComputePurchase {
+    /// The plan to purchase
+    #[arg(long, help = "Plan ID to purchase (see list-plans)")]
+    plan_id: H256,
+
+    /// OS disk image to boot
+    #[arg(long, help = "OS disk image name (see list-images)")]
+    image: String,
+
+    /// CID of a cloud-init configuration
+    #[arg(long, value_name = "CID", help = "CID of a cloud-init configuration to apply on first boot")]
+    cloud_init_cid: Option<String>,
+
+    /// Local cloud-init configuration to upload
+    #[arg(long, value_name = "PATH", conflicts_with = "cloud_init_cid", help = "Local cloud-init configuration to upload to IPFS and apply on first boot")]
+    cloud_init_file: Option<String>,
+
+    /// Base URL of the IPFS HTTP API to upload through
+    #[arg(long, value_name = "URL", requires = "cloud_init_file", help = "IPFS HTTP API to add the cloud-init file through (default: $IPFS_API_URL, else the Hippius relay)")]
+    ipfs_api: Option<String>,
+
+    /// Location to run in
+    #[arg(long, value_name = "ID", help = "Location ID to run the compute space in")]
+    location: Option<u32>,
+
+    /// Miner to run on
+    #[arg(long, help = "Node ID of the compute miner to run on")]
+    miner_id: Option<String>,
+
+    /// Account the plan is bought for
+    #[arg(long, value_name = "SS58_OR_LABEL", help = "Buy the plan for this account (SS58 address or wallet label) instead of the signer")]
+    pay_for: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -218,13 +997,23 @@ enum AccountCommands {
     /// Transfer funds from one account to another
     Transfer {
         /// The account ID to transfer funds to
-        #[arg(help = "Specify the account ID to transfer funds to")]
-        account_id: AccountId32,
+        #[arg(help = "Specify the account ID (SS58 address or wallet label) to transfer funds to")]
+        account_id: String,
 
         /// The amount of funds to transfer
         #[arg(help = "Specify the amount of funds to transfer")]
         amount: u128,
     },
+    /// Transfer the signer's entire free balance to another account
+    TransferAll {
+        /// The account ID to transfer funds to
+        #[arg(help = "Account (SS58 address or wallet label) to move the whole balance to")]
+        account_id: String,
+
+        /// Leave the existential deposit behind so the sender account is not reaped
+        #[arg(long, help = "Keep the existential deposit in the sending account")]
+        keep_alive: bool,
+    },
     Stake {
         /// The amount of funds to stake
         #[arg(help = "Specify the amount to stake")]
@@ -235,13 +1024,108 @@ enum AccountCommands {
         #[arg(help = "Specify the amount to stake in USDT or similar currency")]
         amount: u128,
     },
-    /// Withdraw funds in a different manner 
-    Withdraw {
-        #[arg(help = "Specify the amount to withdraw")]
-        amount: u32,
+    /// Withdraw all unbonded funds whose unlock era has been reached
+    #[command(long_about = "Withdraw all unbonded funds whose unlock era has been reached.\n\nThere is no amount: every matured unbonding chunk is released at once. Use `account staking-info` to see what is withdrawable.")]
+    Withdraw,
+    /// Nominate validators with the signer's bonded stake
+    Nominate {
+        /// The validators to nominate
+        #[arg(required = true, help = "Validator accounts (SS58 addresses or wallet labels) to nominate")]
+        targets: Vec<String>,
+    },
+    /// Stop nominating or validating without unbonding
+    Chill,
+    /// List recent balance transfers to or from an account
+    History {
+        /// The account whose transfers to list
+        #[arg(help = "Account (SS58 address or wallet label) to list transfers for (default: the signer)")]
+        account_id: Option<String>,
+
+        /// First block to scan
+        #[arg(long, value_name = "NUMBER", help = "First block to scan (default: 1000 blocks before --to-block)")]
+        from_block: Option<u32>,
+
+        /// Last block to scan
+        #[arg(long, value_name = "NUMBER", help = "Last block to scan (default: the latest finalized block)")]
+        to_block: Option<u32>,
+    },
+    /// Show the bonded, active and unlocking stake of an account
+    StakingInfo {
+        /// The stash account to query
+        #[arg(help = "Stash account (SS58 address or wallet label) to query (default: the signer)")]
+        account_id: Option<String>,
+    },
+    /// Show the free, reserved, frozen and total balance of an account
+    Balance {
+        /// The account to query
+        #[arg(help = "Account (SS58 address or wallet label) to query (default: the signer)")]
+        account_id: Option<String>,
+    },
+    /// Sweep the balances of all local wallets into one account (requires --yes)
+    DustSweep {
+        /// The account that receives the swept funds
+        #[arg(help = "Account (SS58 address or wallet label) to sweep funds into")]
+        dest: String,
+
+        /// Only sweep wallets whose free balance is above this amount
+        #[arg(long, help = "Minimum free balance to sweep (default: the estimated transfer fee)")]
+        threshold: Option<u128>,
+    },
+}
+
+#[derive(Subcommand)]
+enum WalletCommands {
+    /// Write every hotkey, with its mnemonic, to a backup file
+    Export {
+        /// Backup file to create
+        #[arg(long, value_name = "PATH", help = "Backup file to write (must not exist yet)")]
+        out: String,
+
+        /// Encrypt every mnemonic in the backup with one passphrase
+        #[arg(long, help = "Prompt for a backup passphrase and encrypt all mnemonics with it (encrypted hotkeys are decrypted first)")]
+        encrypt: bool,
+    },
+    /// Restore the hotkeys of a backup file into the keystore
+    Import {
+        /// Backup file written by `wallet export`
+        #[arg(help = "Backup file to restore")]
+        file: String,
     },
 }
 
+#[derive(Subcommand)]
+enum NodeCommands {
+    /// Show the node's peers, sync status and best/finalized blocks
+    Health,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the effective settings after applying flags, env vars and the active profile
+    Show,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum KeyScheme {
+    /// Schnorrkel over Ristretto25519, the Substrate default
+    Sr25519,
+    /// Edwards25519
+    Ed25519,
+    /// ECDSA over secp256k1
+    Ecdsa,
+}
+
+impl KeyScheme {
+    fn name(self) -> &'static str {
+        match self {
+            KeyScheme::Sr25519 => "sr25519",
+            KeyScheme::Ed25519 => "ed25519",
+            KeyScheme::Ecdsa => "ecdsa",
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum MinerCommand {
     /// Fetch compute-related information
@@ -266,265 +1150,486 @@ enum CliNodeType {
     StorageMiner,
 }
 
+/// The runtime's `ProxyType`s, as accepted on the command line
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+enum CliProxyType {
+    /// Any call
+    Any,
+    /// Any call except balance transfers
+    NonTransfer,
+    /// Governance calls only
+    Governance,
+    /// Staking calls only
+    Staking,
+}
+
+impl From<CliProxyType> for ProxyType {
+    fn from(proxy_type: CliProxyType) -> Self {
+        match proxy_type {
+            CliProxyType::Any => ProxyType::Any,
+            CliProxyType::NonTransfer => ProxyType::NonTransfer,
+            CliProxyType::Governance => ProxyType::Governance,
+            CliProxyType::Staking => ProxyType::Staking,
+        }
+    }
+}
+
+/// Sends this crate's log lines to stderr as bare messages, so stdout only
+/// carries command results. `--quiet` keeps errors, `--verbose` adds debug
+/// lines, and RUST_LOG can override either (e.g. `RUST_LOG=subxt=debug`).
+fn init_logging(opts: &GlobalOpts) {
+    let level = if opts.quiet {
+        log::LevelFilter::Error
+    } else if opts.verbose {
+        log::LevelFilter::Debug
+    } else {
+        log::LevelFilter::Info
+    };
+    env_logger::Builder::new()
+        .filter_level(log::LevelFilter::Warn)
+        .filter_module(module_path!(), level)
+        .parse_default_env()
+        .format(|buf, record| writeln!(buf, "{}", record.args()))
+        .init();
+}
+
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() {
     dotenv().ok();
     
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    init_logging(&cli.opts);
+    if let Err(e) = load_profile(&mut cli.opts) {
+        eprintln!("❌ Failed to load config: {}", e);
+        std::process::exit(e.exit_code());
+    }
     
-    match &cli.command {
-        Commands::Storage { 
-            storage_command, 
-            file_hash,
-            file_name,
-            miner_ids, // Add this line
-        } => {
-            if let Err(e) = handle_storage_command(
-                storage_command.clone(), 
-                file_hash.clone(), 
-                file_name.clone(),
-                miner_ids.clone() // Update this line
-            ).await {
-                eprintln!("❌ Failed to perform storage operation: {}", e);
-            }
+    // Every command reports failure the same way: a message and a categorised exit code
+    let (context, result) = run_command(&cli.opts, &cli.command).await;
+
+    if let Err(e) = result {
+        eprintln!("❌ {}: {}", context, e);
+        std::process::exit(e.exit_code());
+    }
+}
+
+/// Runs one command, returning what to prefix its error with and its result.
+async fn run_command(opts: &GlobalOpts, command: &Commands) -> (&'static str, Result<(), CliError>) {
+    match command {
+        Commands::Storage { storage_command: StorageCommands::CostEstimate { cid, size_bytes, periods, ipfs_api } } => {
+            ("Failed to estimate the storage cost", handle_storage_cost_estimate(opts, cid.as_deref(), *size_bytes, *periods, ipfs_api.clone()).await)
         }
-        Commands::ListImages => {
-            handle_list_images().await?;
+        Commands::Storage { storage_command: StorageCommands::Status { cid, account } } => {
+            ("Failed to fetch the storage status", handle_storage_status(opts, account.as_deref(), cid).await)
+        }
+        Commands::Storage { storage_command: StorageCommands::Wait { cid, timeout, replicas, interval, account } } => {
+            ("Failed to wait for the file", handle_storage_wait(opts, account.as_deref(), cid, *timeout, *replicas, *interval).await)
+        }
+        Commands::Storage { storage_command } => {
+            ("Failed to perform storage operation", handle_storage_command(opts, storage_command).await)
+        }
+        Commands::ListImages { contains } => {
+            ("Failed to list images", handle_list_images(opts, contains.as_deref()).await)
         }
         Commands::GetCredits => {
-            handle_get_credits().await?;
+            ("Failed to get credits", handle_get_credits(opts).await)
         }
         Commands::InsertKey { seed_phrase, public_key } => {
-            handle_insert_key(seed_phrase.to_string(), public_key.to_string()).await?;
+            ("Failed to insert key", handle_insert_key(opts, seed_phrase.to_string(), public_key.to_string()).await)
+        }
+        // clap rejects --node-id together with --account or --all
+        Commands::GetNodeInfo { node_id: Some(node_id), .. } => {
+            ("Failed to get node info", handle_query_node_by_id(opts, node_id).await)
         }
-        Commands::GetNodeInfo => {
-            handle_query_my_node().await?;
+        Commands::GetNodeInfo { account, all, .. } => {
+            ("Failed to get node info", handle_query_node_info(opts, account.as_deref(), *all).await)
         }
         Commands::Miner { miner_command } => {
             match miner_command {
                 MinerCommand::Compute => {
-                    if let Err(e) = handle_compute_infos().await {
-                        eprintln!("❌ Error fetching compute information: {}", e);
-                        std::process::exit(1);
-                    }
+                    ("Error fetching compute information", handle_compute_infos(opts).await)
                 }
                 MinerCommand::Storage => {
-                    if let Err(e) = handle_storage_infos().await {
-                        eprintln!("❌ Error fetching storage information: {}", e);
-                        std::process::exit(1);
-                    }
+                    ("Error fetching storage information", handle_storage_infos(opts).await)
                 }
                 MinerCommand::RegisterComputeMiner => {
-                    if let Err(e) = handle_register_compute_miner_info().await {
-                        eprintln!("❌ Error displaying compute miner registration info: {}", e);
-                        std::process::exit(1);
-                    }
+                    ("Error displaying compute miner registration info", handle_register_compute_miner_info().await)
                 }
                 MinerCommand::RegisterStorageMiner => {
-                    if let Err(e) = handle_register_storage_miner_info().await {
-                        eprintln!("❌ Error displaying storage miner registration info: {}", e);
-                        std::process::exit(1);
-                    }
+                    ("Error displaying storage miner registration info", handle_register_storage_miner_info().await)
                 }
                 MinerCommand::RegisterValidator => {
-                    if let Err(e) = handle_register_validator_info().await {
-                        eprintln!("❌ Error displaying validator registration info: {}", e);
-                        std::process::exit(1);
-                    }
+                    ("Error displaying validator registration info", handle_register_validator_info().await)
                 }
             }
         }
-        Commands::GetRankings { node_type, node_id } => {
-            if let Err(e) = handle_get_rankings(*node_type, node_id.clone()).await {
-                eprintln!("❌ Failed to get rankings: {}", e);
-            }
+        Commands::GetRankings { node_type, node_id, all: _, since_block } => {
+            ("Failed to get rankings", handle_get_rankings(opts, *node_type, node_id.as_deref(), *since_block).await)
         }
-        Commands::RegisterNodeWithColdkey { node_type, node_id, pay_in_credits, ipfs_node_id } => {
-            if let Err(e) = handle_register_node_with_coldkey(*node_type, node_id.clone(), *pay_in_credits, ipfs_node_id.clone()).await {
-                eprintln!("❌ Failed to register node: {}", e);
-            }
+        Commands::RewardPool { node_type } => {
+            ("Failed to get reward pool", handle_reward_pool(opts, *node_type).await)
         }
-        Commands::RegisterNodeWithHotkey { hips_key, hotkey_address, node_type, node_id, pay_in_credits, ipfs_node_id } => {
-            if let Err(e) = handle_register_node_with_hotkey( hotkey_address, hips_key, *node_type, node_id.clone(), *pay_in_credits, ipfs_node_id.clone()).await {
-                eprintln!("❌ Failed to register node: {}", e);
-            }
+        Commands::WatchRewards { node_type, node_id, interval, once } => {
+            ("Failed to watch rewards", handle_watch_rewards(opts, *node_type, node_id, *interval, *once).await)
         }
-        Commands::GenerateKeys => {
-            if let Err(e) = handle_generate_keys().await {
-                eprintln!("❌ Failed to generate keys: {}", e);
-                std::process::exit(1);
-            }
+        Commands::RegisterNodeWithColdkey { node_type, node_id, pay_in_credits, ipfs_node_id, auto_detect } => {
+            let result = match resolve_node_ids(opts, *node_type, node_id.clone(), ipfs_node_id.clone(), *auto_detect).await {
+                Ok((node_id, ipfs_node_id)) => handle_register_node_with_coldkey(opts, *node_type, node_id, *pay_in_credits, ipfs_node_id).await,
+                Err(e) => Err(e),
+            };
+            ("Failed to register node", result)
+        }
+        Commands::RegisterNodeWithHotkey { hips_key, hotkey_address, node_type, node_id, pay_in_credits, ipfs_node_id, auto_detect } => {
+            let result = match resolve_node_ids(opts, *node_type, node_id.clone(), ipfs_node_id.clone(), *auto_detect).await {
+                Ok((node_id, ipfs_node_id)) => handle_register_node_with_hotkey(opts, hotkey_address, hips_key, *node_type, node_id, *pay_in_credits, ipfs_node_id).await,
+                Err(e) => Err(e),
+            };
+            ("Failed to register node", result)
+        }
+        Commands::GenerateKeys { out_dir, scheme, word_count } => {
+            ("Failed to generate keys", handle_generate_keys(opts, out_dir.clone(), *scheme, *word_count).await)
         }
-        // Commands::LockCredits { amount } => {
-        //     if let Err(e) = handle_lock_credits(*amount).await {
-        //         eprintln!("❌ Failed to lock credits: {}", e);
-        //         std::process::exit(1);
-        //     }
-        // }
         Commands::ListLockedCredits => {
-            if let Err(e) = handle_list_locked_credits().await {
-                eprintln!("❌ Failed to list locked credits: {}", e);
-                std::process::exit(1);
-            }
+            ("Failed to list locked credits", handle_list_locked_credits(opts).await)
+        }
+        Commands::FulfillLockedCredit { lock_id, account, tx_hash } => {
+            ("Failed to fulfill locked credit", handle_fulfill_locked_credit(opts, *lock_id, account, tx_hash).await)
+        }
+        Commands::Batch { file } => {
+            ("Batch failed", handle_batch(opts, file).await)
         }
-        Commands::BulkUpload { csv_path } => {
-            handle_bulk_upload(csv_path).await?;
+        Commands::BulkUpload { csv_path, skip_invalid, batch_size, replicas } => {
+            ("Bulk upload failed", handle_bulk_upload(opts, csv_path, *skip_invalid, *batch_size as usize, *replicas).await)
+        }
+        Commands::ShowPlan { plan_id } => {
+            ("Failed to show plan", handle_show_plan(opts, *plan_id).await)
         }
         Commands::ListPlans => {
-            handle_list_plans().await?;
+            ("Failed to list plans", handle_list_plans(opts).await)
         }
-        Commands::ListIpfsFiles => {
-            handle_list_ipfs_files().await?;
+        Commands::ListIpfsFiles { diff, limit, offset, grep } => {
+            ("Failed to list IPFS files", handle_list_ipfs_files(opts, diff.as_deref(), *limit, *offset, grep.as_deref()).await)
         }
         Commands::GetCurrentLockPeriod => {
-            handle_get_current_lock_period().await?;
+            ("Failed to get the current lock period", handle_get_current_lock_period(opts).await)
         }
         Commands::GetMinLockAmount => {
-            handle_get_min_lock_amount().await?;
+            ("Failed to get the minimum lock amount", handle_get_min_lock_amount(opts).await)
         }
+        Commands::Compute { compute_command } => match compute_command {
+            ComputeCommands::Boot { plan_id } => ("Failed to request boot", handle_request_boot(opts, *plan_id).await),
+            ComputeCommands::Reboot { plan_id } => ("Failed to request reboot", handle_request_reboot(opts, *plan_id).await),
+            ComputeCommands::Stop { plan_id } => ("Failed to request stop", handle_request_stop(opts, *plan_id).await),
+            ComputeCommands::Delete { plan_id } => ("Failed to request delete", handle_request_delete(opts, *plan_id).await),
+            ComputeCommands::Purchase(purchase) => ("Failed to purchase plan", handle_purchase_compute_plan(opts, purchase).await),
+            ComputeCommands::ListInstances => ("Failed to list compute instances", handle_list_instances(opts).await),
+        },
         Commands::Account { account_command } => {
             match account_command {
                 AccountCommands::Transfer { account_id, amount } => {
-                    if let Err(e) = handle_transfer(account_id.clone(), *amount).await {
-                        eprintln!("❌ Failed to transfer funds: {}", e);
-                    }
+                    ("Failed to transfer funds", handle_transfer(opts, account_id, *amount).await)
+                }
+                AccountCommands::TransferAll { account_id, keep_alive } => {
+                    ("Failed to transfer funds", handle_transfer_all(opts, account_id, *keep_alive).await)
                 }
                 AccountCommands::Stake { amount } => {
-                    if let Err(e) = handle_stake(*amount).await {
-                        eprintln!("❌ Failed to stake funds: {}", e);
-                    }
+                    ("Failed to stake funds", handle_stake(opts, *amount).await)
                 }
                 AccountCommands::UnStake { amount } => {
-                    if let Err(e) = handle_un_stake(*amount).await {
-                        eprintln!("❌ Failed to unStake funds: {}", e);
-                    }
+                    ("Failed to unStake funds", handle_un_stake(opts, *amount).await)
                 }
-                AccountCommands::Withdraw { amount } => {
-                    if let Err(e) = handle_withdraw(*amount).await {
-                        eprintln!("❌ Failed to withdraw funds: {}", e);
-                    }
+                AccountCommands::Withdraw => {
+                    ("Failed to withdraw funds", handle_withdraw(opts).await)
+                }
+                AccountCommands::Nominate { targets } => {
+                    ("Failed to nominate validators", handle_nominate(opts, targets).await)
+                }
+                AccountCommands::Chill => {
+                    ("Failed to chill", handle_chill(opts).await)
+                }
+                AccountCommands::History { account_id, from_block, to_block } => {
+                    ("Failed to fetch transfer history", handle_history(opts, account_id.as_deref(), *from_block, *to_block).await)
+                }
+                AccountCommands::StakingInfo { account_id } => {
+                    ("Failed to fetch staking info", handle_staking_info(opts, account_id.as_deref()).await)
+                }
+                AccountCommands::Balance { account_id } => {
+                    ("Failed to fetch balance", handle_balance(opts, account_id.as_deref()).await)
+                }
+                AccountCommands::DustSweep { dest, threshold } => {
+                    ("Failed to sweep wallets", handle_dust_sweep(opts, dest, *threshold).await)
                 }
             }
         }
         Commands::GetNodeId => {
-            if let Err(e) = handle_get_node_id().await {
-                eprintln!("❌ Failed to get node ID: {}", e);
-            }
+            ("Failed to get node ID", handle_get_node_id(opts).await)
         }
-        Commands::GetIpfsNodeId => {
-            if let Err(e) = handle_get_ipfs_node_id().await {
-                eprintln!("❌ Failed to get IPFS Node ID: {}", e);
-            }
+        Commands::GetIpfsNodeId { ipfs_api } => {
+            ("Failed to get IPFS Node ID", handle_get_ipfs_node_id(ipfs_api.clone()).await)
         }
         Commands::GetHipsKey => {
-            if let Err(e) = handle_get_hips_key().await {
-                eprintln!("❌ Failed to get HIPS key: {}", e);
-            }
+            ("Failed to get HIPS key", handle_get_hips_key(opts).await)
         }
-        Commands::CreateHotkey => {
-            match create_hotkey().await {
-                Ok(hotkey_address) => {
-                    println!("🔑 Hotkey created successfully!");
-                    println!("📍 Hotkey Address: {}", hotkey_address);
+        Commands::CreateHotkey { name, encrypt, word_count, proxy_type, delay } => {
+            let result = match create_hotkey(opts, name.clone(), *encrypt, *word_count, (*proxy_type).into(), *delay).await {
+                Ok(hotkey) if opts.is_json() => print_json(opts, &hotkey),
+                Ok(hotkey) => {
+                    log::info!("🔑 Hotkey created successfully!");
+                    println!("📍 Hotkey Address: {}", hotkey.address);
+                    Ok(())
                 }
-                Err(e) => {
-                    eprintln!("❌ Failed to create hotkey: {}", e);
-                }
-            }
+                Err(e) => Err(e),
+            };
+            ("Failed to create hotkey", result)
         },
-        Commands::ListWallets => {
-            // Logic to list wallets
-            list_wallets().await?;
+        Commands::ImportHotkey { name, mnemonic_file, encrypt, register_proxy, proxy_type, delay } => {
+            let proxy = register_proxy.then(|| (ProxyType::from(*proxy_type), *delay));
+            let result = match import_hotkey(opts, name.clone(), mnemonic_file.as_deref(), *encrypt, proxy).await {
+                Ok(hotkey) if opts.is_json() => print_json(opts, &hotkey),
+                Ok(hotkey) => {
+                    log::info!("🔑 Hotkey imported successfully!");
+                    println!("📍 Hotkey Address: {}", hotkey.address);
+                    Ok(())
+                }
+                Err(e) => Err(e),
+            };
+            ("Failed to import hotkey", result)
         },
-        Commands::SwapNodeOwner { node_id, new_owner, signer_account } => {
-            if let Err(e) = handle_swap_node_owner(node_id.clone(), new_owner.clone(), signer_account.clone()).await {
-                eprintln!("❌ Failed to swap node owner: {}", e);
+        Commands::Proxy { proxy_command: ProxyCommands::Call { real, hotkey, command } } => {
+            let command = ProxiedCommand::try_parse_from(std::iter::once("hipc proxy call").chain(command.iter().map(String::as_str)))
+                .unwrap_or_else(|e| e.exit())
+                .command;
+            if matches!(command, Commands::Proxy { .. }) {
+                return ("Failed to submit proxied call", Err(CliError::Invalid("proxy calls cannot be nested".to_string())));
             }
+            let real = match resolve_account(opts, real) {
+                Ok(real) => real,
+                Err(e) => return ("Failed to submit proxied call", Err(e)),
+            };
+            let opts = GlobalOpts { proxy: Some(ProxiedSigner { real, hotkey: hotkey.clone() }), ..opts.clone() };
+            Box::pin(run_command(&opts, &command)).await
+        },
+        Commands::ListProxies { account } => {
+            ("Failed to list proxies", handle_list_proxies(opts, account.as_deref()).await)
+        },
+        Commands::RemoveHotkey { hotkey } => {
+            ("Failed to remove hotkey", handle_remove_hotkey(opts, hotkey).await)
+        },
+        Commands::ExportHotkey { hotkey, i_understand_the_risk } => {
+            ("Failed to export hotkey", handle_export_hotkey(opts, hotkey, *i_understand_the_risk))
+        },
+        Commands::ListWallets => {
+            ("Failed to list wallets", list_wallets(opts).await)
+        },
+        Commands::Wallet { wallet_command } => match wallet_command {
+            WalletCommands::Export { out, encrypt } => ("Failed to export wallets", handle_wallet_export(opts, out, *encrypt)),
+            WalletCommands::Import { file } => ("Failed to import wallets", handle_wallet_import(opts, file)),
+        },
+        Commands::VerifyKeystore => {
+            ("Keystore verification failed", handle_verify_keystore(opts))
+        },
+        Commands::SwapNodeOwner { node_id, new_owner } => {
+            ("Failed to swap node owner", handle_swap_node_owner(opts, node_id.clone(), new_owner).await)
         },
         Commands::UploadToIpfs { file_path } => {
-            if let Err(e) = handle_upload_to_ipfs(file_path.clone()).await {
-                eprintln!("❌ Failed to upload file to IPFS: {}", e);
-            }
+            ("Failed to upload file to IPFS", handle_upload_to_ipfs(file_path.clone()).await)
+        },
+        Commands::Completion { shell } => {
+            // Buffered so a closed stdout is reported rather than panicking inside clap_complete
+            let mut script = Vec::new();
+            clap_complete::generate(*shell, &mut Cli::command(), "hipc", &mut script);
+            ("Failed to generate completions", std::io::stdout().write_all(&script).map_err(CliError::from))
+        },
+        Commands::Config { config_command } => match config_command {
+            ConfigCommands::Show => ("Failed to show config", handle_config_show(opts)),
+        },
+        Commands::Node { node_command: NodeCommands::Health } => {
+            ("Failed to check node health", handle_node_health(opts).await)
         },
+        Commands::Doctor { fix } => {
+            ("Failed to check the setup", handle_doctor(opts, *fix).await)
+        },
+        Commands::WatchEvents { pallet, mine, from_block } => {
+            ("Failed to watch events", handle_watch_events(opts, pallet.as_deref(), *mine, *from_block).await)
+        },
+        Commands::Whoami => {
+            ("Failed to show identity", handle_whoami(opts).await)
+        },
+        Commands::SignMessage { message, hotkey } => {
+            ("Failed to sign message", handle_sign_message(opts, message.as_deref(), hotkey.as_deref()))
+        },
+        Commands::VerifyMessage { message, signature, account } => {
+            ("Failed to verify message", handle_verify_message(opts, message, signature, account))
+        },
+        Commands::DecodeCall { hex } => {
+            ("Failed to decode call", handle_decode_call(opts, hex))
+        },
+    }
+}
+
+/// Releases every matured unbonding chunk of the signer with `staking.withdraw_unbonded`.
+async fn handle_withdraw(opts: &GlobalOpts) -> Result<(), CliError> {
+    log::info!("💰 Initiating withdrawal of unbonded funds");
+    
+    let (api, signer) = setup_substrate_client(opts).await?;
+    let num_slashing_spans = query_slashing_spans(&api, signer.account_id()).await?;
+
+    if !opts.dry_run && !confirm(opts, "Withdraw all matured unbonded funds?")? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
+
+    let tx = custom_runtime::tx()
+        .staking()
+        .withdraw_unbonded(num_slashing_spans);
+
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
     }
     
+    log::info!("✅ Successfully withdrew unbonded funds");
     Ok(())
 }
 
-async fn handle_withdraw(amount: u32) -> Result<(), Box<dyn std::error::Error>> {
-    println!("💰 Initiating usStake of amount: {}", amount);
-    
-    let (api, signer) = setup_substrate_client().await?;
 
-    // Create the usStake transaction
+
+/// Nominates `targets` with `staking.nominate`. Targets that are not registered
+/// validators are warned about but still submitted; the chain has the final say.
+async fn handle_nominate(opts: &GlobalOpts, targets: &[String]) -> Result<(), CliError> {
+    let targets = targets
+        .iter()
+        .map(|target| resolve_account(opts, target))
+        .collect::<Result<Vec<_>, _>>()?;
+    log::info!("🗳️  Nominating {} validator(s)", targets.len());
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+    let storage = api.storage().at_latest().await?;
+
+    for target in &targets {
+        let query = subxt::dynamic::storage("Staking", "Validators", vec![subxt::dynamic::Value::from_bytes(target.encode())]);
+        if storage.fetch(&query).await?.is_some() {
+            log::info!("  ✅ {}", target);
+        } else {
+            log::warn!("  ⚠️ {} is not a registered validator", target);
+        }
+    }
+
+    if !opts.dry_run && !confirm(opts, &format!("Nominate these {} validator(s)?", targets.len()))? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
+
     let tx = custom_runtime::tx()
         .staking()
-        .withdraw_unbonded(amount); // Specify the amount to stake
+        .nominate(targets.into_iter().map(subxt::utils::MultiAddress::Id).collect());
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
 
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
-    
-    println!("✅ Successfully withdrew amount: {}", amount);
+    log::info!("✅ Successfully submitted nominations");
     Ok(())
 }
 
+/// Stops the signer's nominations (or validator intent) with `staking.chill`,
+/// leaving the bond in place.
+async fn handle_chill(opts: &GlobalOpts) -> Result<(), CliError> {
+    log::info!("🧊 Initiating chill");
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+    let storage = api.storage().at_latest().await?;
+    let stash = subxt::dynamic::Value::from_bytes(signer.account_id().encode());
+
+    let nominations = match storage.fetch(&subxt::dynamic::storage("Staking", "Nominators", vec![stash.clone()])).await? {
+        Some(value) => Some(value.as_type::<Nominations>()?),
+        None => None,
+    };
+    let validating = storage.fetch(&subxt::dynamic::storage("Staking", "Validators", vec![stash])).await?.is_some();
+
+    match &nominations {
+        Some(nominations) => log::info!("🗳️  Currently nominating {} validator(s)", nominations.targets.0.len()),
+        None if validating => log::info!("🛡️  Currently validating"),
+        None => log::info!("ℹ️ Not nominating or validating; chilling has no effect"),
+    }
+
+    if !opts.dry_run && !confirm(opts, "Stop nominating/validating? Your stake stays bonded.")? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
+
+    let tx = custom_runtime::tx().staking().chill();
+
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
+
+    log::info!("✅ Successfully chilled; your stake remains bonded");
+    Ok(())
+}
 
+/// The `num_slashing_spans` witness `withdraw_unbonded` expects for `stash`:
+/// all prior spans plus the current one, or 0 if it was never slashed.
+async fn query_slashing_spans(api: &OnlineClient<PolkadotConfig>, stash: &AccountId32) -> Result<u32, CliError> {
+    let query = subxt::dynamic::storage("Staking", "SlashingSpans", vec![subxt::dynamic::Value::from_bytes(stash.encode())]);
+    match api.storage().at_latest().await?.fetch(&query).await? {
+        Some(value) => Ok(value.as_type::<SlashingSpans>()?.prior.len() as u32 + 1),
+        None => Ok(0),
+    }
+}
 
-async fn handle_un_stake(amount: u128) -> Result<(), Box<dyn std::error::Error>> {
-    println!("💰 Initiating usStake of amount: {}", amount);
+async fn handle_un_stake(opts: &GlobalOpts, amount: u128) -> Result<(), CliError> {
+    log::info!("💰 Initiating usStake of amount: {}", amount);
     
-    let (api, signer) = setup_substrate_client().await?;
+    let (api, signer) = setup_substrate_client(opts).await?;
+
+    if !opts.dry_run && !confirm(opts, &format!("Unstake {}?", amount))? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
 
     // Create the usStake transaction
     let tx = custom_runtime::tx()
         .staking()
         .unbond(amount); // Specify the amount to stake
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
     
-    println!("✅ Successfully usStaked amount: {}", amount);
+    log::info!("✅ Successfully usStaked amount: {}", amount);
     Ok(())
 }
 
-async fn handle_stake(amount: u128) -> Result<(), Box<dyn std::error::Error>> {
-    println!("💰 Initiating stake of amount: {}", amount);
+async fn handle_stake(opts: &GlobalOpts, amount: u128) -> Result<(), CliError> {
+    log::info!("💰 Initiating stake of amount: {}", amount);
     
-    let (api, signer) = setup_substrate_client().await?;
+    let (api, signer) = setup_substrate_client(opts).await?;
+
+    if !opts.dry_run && !confirm(opts, &format!("Stake {}?", amount))? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
 
     // Create the stake transaction
     let tx = custom_runtime::tx()
         .staking()
         .bond(amount, Staked); // Specify the amount to stake
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
     
-    println!("✅ Successfully staked amount: {}", amount);
+    log::info!("✅ Successfully staked amount: {}", amount);
     Ok(())
 }
 
+#[allow(dead_code)] // not yet exposed as a CLI command
 fn handle_docker_command(docker_command: String, args: Vec<String>) {
     // Default URL prefix for your registry
     let registry_url = "localhost:3000";
 
-    println!("🐳 Executing Docker command: {}", docker_command);
-    println!("📦 Arguments: {}", args.join(" "));
+    log::info!("🐳 Executing Docker command: {}", docker_command);
+    log::info!("📦 Arguments: {}", args.join(" "));
 
     // Transform arguments, adding the registry URL for specific commands like "push" or "pull"
     let transformed_args: Vec<String> = args
@@ -532,7 +1637,7 @@ fn handle_docker_command(docker_command: String, args: Vec<String>) {
         .map(|arg| {
             if arg.contains(':') && (docker_command == "push" || docker_command == "pull") {
                 let modified_arg = format!("{}/{}", registry_url, arg);
-                println!("🌐 Modifying image path to: {}", modified_arg);
+                log::info!("🌐 Modifying image path to: {}", modified_arg);
                 modified_arg
             } else {
                 arg
@@ -541,7 +1646,7 @@ fn handle_docker_command(docker_command: String, args: Vec<String>) {
         .collect();
 
     // Execute the transformed Docker command
-    println!("🚀 Running docker {} {}...", docker_command, transformed_args.join(" "));
+    log::info!("🚀 Running docker {} {}...", docker_command, transformed_args.join(" "));
     let output = Command::new("docker")
         .arg(docker_command.clone())
         .args(transformed_args)
@@ -559,7 +1664,7 @@ fn handle_docker_command(docker_command: String, args: Vec<String>) {
             }
             
             if output.status.success() {
-                println!("✅ Docker command completed successfully!");
+                log::info!("✅ Docker command completed successfully!");
             } else {
                 eprintln!("❌ Docker command failed with exit code: {}", output.status.code().unwrap_or(-1));
             }
@@ -570,66 +1675,165 @@ fn handle_docker_command(docker_command: String, args: Vec<String>) {
     }
 }
 
-async fn handle_create_docker_space(name: String) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Initializing Docker space creation for: {}", name);
+#[allow(dead_code)] // not yet exposed as a CLI command
+async fn handle_create_docker_space(opts: &GlobalOpts, name: String) -> Result<(), CliError> {
+    log::info!("🚀 Initializing Docker space creation for: {}", name);
     
-    let (api, signer) = setup_substrate_client().await?;
+    let (api, signer) = setup_substrate_client(opts).await?;
     
-    println!("📤 Submitting transaction to create Docker space...");
+    log::info!("📤 Submitting transaction to create Docker space...");
     let tx = custom_runtime::tx().container_registry().create_space(name.clone().into_bytes());
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-    
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
     
-    println!("✅ Successfully created Docker space!");
-    println!("📦 Space Name: {}", name);
+    log::info!("✅ Successfully created Docker space!");
+    log::info!("📦 Space Name: {}", name);
 
     Ok(())
 }
 
-const KEYSTORE_PATH: &str = "/opt/hippius/data/chains/hippius-mainnet/keystore/";
+const MAINNET_KEYSTORE_PATH: &str = "/opt/hippius/data/chains/hippius-mainnet/keystore/";
+const TESTNET_KEYSTORE_PATH: &str = "/opt/hippius/data/chains/hippius-testnet/keystore/";
+
+/// Public endpoint used when no `--rpc-url`, SUBSTRATE_NODE_URL or profile is set
+const DEFAULT_RPC_URL: &str = "wss://rpc.hippius.network";
+/// The operator's own node, for node-local RPCs (`author_insertKey`, `system_localPeerId`)
+const LOCAL_NODE_RPC_URL: &str = "http://127.0.0.1:9944";
 
 fn get_hotkeys_dir() -> String {
     let home_path = home_dir().expect("Could not find home directory");
     home_path.join("hippius/keystore/hotkeys").to_str().unwrap().to_string()
 }
 
+/// A hotkey stored in the local keystore. Never carries the secret itself.
+#[derive(Serialize, Clone)]
+struct HotkeyInfo {
+    label: String,
+    name: String,
+    ss58_address: String,
+    derivation: HotkeyDerivation,
+    encrypted: bool,
+}
+
+/// How the secret behind a hotkey was produced
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum HotkeyDerivation {
+    /// An independent BIP39 mnemonic generated for this hotkey alone
+    Mnemonic,
+}
+
+/// Key material produced by `generate-keys` or `create-hotkey`
+#[derive(Serialize)]
+struct GeneratedKey {
+    address: String,
+    scheme: KeyScheme,
+    public_key_hex: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mnemonic: Option<String>,
+    keystore_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proxy_tx_hash: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WalletListing {
+    /// SS58 address of the HIPS key
+    coldkey: Option<String>,
+    /// Keystore file name of the HIPS key (`68697073` ++ hex public key)
+    coldkey_file: Option<String>,
+    hotkeys: Vec<HotkeyInfo>,
+}
+
 /// Lists all wallets: the HIPS key (coldkey) and associated hotkeys.
-async fn list_wallets() -> Result<(), Box<dyn std::error::Error>> {
+async fn list_wallets(opts: &GlobalOpts) -> Result<(), CliError> {
+    let coldkey_file = find_hips_key(&opts.keystore_path())?;
+    let coldkey = coldkey_file.as_deref().and_then(hips_key_address);
+    let hotkeys = if coldkey_file.is_some() {
+        find_hotkeys(&get_hotkeys_dir())?
+    } else {
+        Vec::new()
+    };
+
+    if opts.is_json() {
+        print_json(opts, &WalletListing { coldkey, coldkey_file, hotkeys })?;
+        return Ok(());
+    }
+
     println!("Wallets");
 
     // Find the coldkey (HIPS key)
-    let coldkey = find_hips_key(KEYSTORE_PATH)?;
-    if let Some(coldkey) = coldkey {
-        println!("└── Coldkey {}  ss58_address {}", "hips-key", coldkey);
+    if let Some(coldkey_file) = coldkey_file {
+        match coldkey {
+            Some(address) => println!("└── Coldkey {}  ss58_address {}", COLDKEY_LABEL, address),
+            None => println!("└── Coldkey {}  file {}", COLDKEY_LABEL, coldkey_file),
+        }
     } else {
         println!("❌ No HIPS key (coldkey) found.");
         return Ok(());
     }
 
     // List all hotkeys
-    let hotkeys_dir = get_hotkeys_dir();
-    let hotkeys = find_hotkeys(&hotkeys_dir)?;
-    for (i, (name, address)) in hotkeys.iter().enumerate() {
+    for (i, hotkey) in hotkeys.iter().enumerate() {
         if i == hotkeys.len() - 1 {
-            println!("    └── Hotkey {}  ss58_address {}", name, address);
+            println!("    └── Hotkey {}  ss58_address {}", hotkey.name, hotkey.ss58_address);
         } else {
-            println!("    ├── Hotkey {}  ss58_address {}", name, address);
+            println!("    ├── Hotkey {}  ss58_address {}", hotkey.name, hotkey.ss58_address);
         }
     }
 
     Ok(())
 }
 
+/// Renders on-chain bytes for display: the UTF-8 string when valid, otherwise
+/// a `0x`-prefixed hex dump so binary values are never lost.
+fn bytes_to_display(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => format!("0x{}", hex::encode(bytes)),
+    }
+}
+
+/// Serializes a command result to stdout as JSON, indented under `json-pretty`.
+fn print_json<T: Serialize>(opts: &GlobalOpts, value: &T) -> Result<(), CliError> {
+    if opts.output == OutputFormat::JsonPretty {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    } else {
+        println!("{}", serde_json::to_string(value)?);
+    }
+    Ok(())
+}
+
+/// Writes `rows` to stdout as CSV with a header row. An empty list still gets
+/// its header, taken from `T::default()`, so consumers always see the columns.
+fn print_csv<T: Serialize + Default>(rows: &[T]) -> Result<(), CliError> {
+    let mut out = std::io::stdout().lock();
+    out.write_all(&csv_bytes(rows)?)?;
+    Ok(())
+}
+
+fn csv_bytes<T: Serialize + Default>(rows: &[T]) -> Result<Vec<u8>, CliError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    if rows.is_empty() {
+        writer.serialize(T::default())?;
+    }
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    let mut bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    if rows.is_empty() {
+        let header_end = bytes.iter().position(|&b| b == b'\n').map_or(bytes.len(), |i| i + 1);
+        bytes.truncate(header_end);
+    }
+    Ok(bytes)
+}
+
 /// Finds the HIPS key (coldkey) by checking files with the "68697073" prefix.
-fn find_hips_key(keystore_path: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+fn find_hips_key(keystore_path: &str) -> Result<Option<String>, CliError> {
     let target_prefix = "68697073"; // "hips" in hex
-    let dir_entries = fs::read_dir(keystore_path)?;
+    let dir_entries = read_keystore_dir(keystore_path)?;
 
     for entry in dir_entries {
         let entry = entry?;
@@ -646,1121 +1850,3799 @@ fn find_hips_key(keystore_path: &str) -> Result<Option<String>, Box<dyn std::err
     Ok(None)
 }
 
-/// Finds all hotkeys stored in the hotkeys directory.
-fn find_hotkeys(hotkeys_dir: &str) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+/// Finds all hotkeys stored in the hotkeys directory, reading each file to
+/// derive its real address. Files that don't hold a valid mnemonic are skipped.
+fn find_hotkeys(hotkeys_dir: &str) -> Result<Vec<HotkeyInfo>, CliError> {
     let mut hotkeys = Vec::new();
 
     if Path::new(hotkeys_dir).exists() {
         for entry in fs::read_dir(hotkeys_dir)? {
             let entry = entry?;
             let path = entry.path();
-            if path.is_file() {
-                if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-                    let hotkey_name = file_name.to_string(); // Adjust naming if needed
-                    let hotkey_address = file_name.to_string(); // Adjust address retrieval
-                    hotkeys.push((hotkey_name, hotkey_address));
-                }
+            if !path.is_file() {
+                continue;
             }
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            // Named hotkeys are stored as `{name}.json`; other `.json` files are
+            // metadata sidecars of legacy address-named hotkeys
+            if let Some(stem) = file_name.strip_suffix(".json") {
+                let hotkey_file = match read_hotkey_file(&path) {
+                    Ok(hotkey_file) => hotkey_file,
+                    // Sidecars only hold a name and are read with their hotkey below
+                    Err(_) if Path::new(hotkeys_dir).join(stem).is_file() => continue,
+                    Err(e) => {
+                        log::warn!("⚠️ Skipping hotkey file {}: {}", path.display(), e);
+                        continue;
+                    }
+                };
+                // Encrypted hotkeys can't be derived without the passphrase, so trust the stored address
+                let (ss58_address, encrypted) = match (&hotkey_file.mnemonic, &hotkey_file.encrypted_mnemonic) {
+                    (Some(mnemonic), _) => match hotkey_pair_from_mnemonic(mnemonic) {
+                        Ok(pair) => (pair.public().to_ss58check(), false),
+                        Err(e) => {
+                            log::warn!("⚠️ Skipping hotkey file {}: {}", path.display(), e);
+                            continue;
+                        }
+                    },
+                    (None, Some(_)) => (hotkey_file.address, true),
+                    (None, None) => {
+                        log::warn!("⚠️ Skipping hotkey file {}: no mnemonic", path.display());
+                        continue;
+                    }
+                };
+                hotkeys.push(HotkeyInfo {
+                    label: file_name.to_string(),
+                    name: hotkey_file.name,
+                    ss58_address,
+                    derivation: HotkeyDerivation::Mnemonic,
+                    encrypted,
+                });
+                continue;
+            }
+
+            let pair = match fs::read_to_string(&path)
+                .map_err(|e| e.into())
+                .and_then(|mnemonic| hotkey_pair_from_mnemonic(&mnemonic))
+            {
+                Ok(pair) => pair,
+                Err(e) => {
+                    log::warn!("⚠️ Skipping hotkey file {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+
+            let ss58_address = pair.public().to_ss58check();
+            hotkeys.push(HotkeyInfo {
+                label: file_name.to_string(),
+                name: read_hotkey_name(&path).unwrap_or_else(|| short_address(&ss58_address)),
+                ss58_address,
+                derivation: HotkeyDerivation::Mnemonic,
+                encrypted: false,
+            });
         }
     }
 
+    hotkeys.sort_by(|a, b| a.label.cmp(&b.label));
     Ok(hotkeys)
 }
 
-async fn create_hotkey() -> Result<String, Box<dyn std::error::Error>> {
-    // Generate a new mnemonic
-    let mnemonic = generate_mnemonic();
-    
-    // Generate keypair from mnemonic
-    let seed = Mnemonic::parse_in_normalized(Language::English, &mnemonic)?.to_seed("");
-    
-    // Use the first 32 bytes of the seed for the sr25519 keypair
-    let seed_array: [u8; 32] = seed[..32].try_into().map_err(|_| "Seed slice has incorrect length")?;
-    let keypair = sr25519::Pair::from_seed(&seed_array);
-
-    // Create hotkey address
-    let hotkey_address = keypair.public().to_ss58check();
-
-    // Ensure hotkeys directory exists
-    let hotkeys_dir = get_hotkeys_dir();
-    fs::create_dir_all(&hotkeys_dir)?;
+#[derive(Serialize)]
+struct HotkeyCheck {
+    file: String,
+    address: Option<String>,
+    encrypted: bool,
+    problem: Option<String>,
+}
 
-    // Save the hotkey to keystore
-    let hotkey_path = format!("{}/{}", hotkeys_dir, hotkey_address);
-    let mut file = fs::File::create(&hotkey_path)?;
-    writeln!(file, "{}", mnemonic)?;
+/// Loads every file in `hotkeys_dir` the way signing would, reporting files that
+/// are empty, do not parse, or whose derived address differs from the address
+/// they are stored under. Encrypted hotkeys are only checked for a readable
+/// address, since deriving them needs the passphrase.
+fn verify_hotkeys(hotkeys_dir: &str) -> Result<Vec<HotkeyCheck>, CliError> {
+    let mut checks = Vec::new();
+    if !Path::new(hotkeys_dir).exists() {
+        return Ok(checks);
+    }
 
-    // Print the mnemonic to the user
-    println!("📝 Mnemonic: {}", mnemonic);
+    for entry in fs::read_dir(hotkeys_dir)? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if !path.is_file() {
+            continue;
+        }
+        // Sidecars of legacy hotkeys only hold a name; they are checked with their hotkey
+        if let Some(hotkey) = file_name.strip_suffix(".json") {
+            if Path::new(hotkeys_dir).join(hotkey).is_file() {
+                continue;
+            }
+        }
 
-    // Warning about storing the mnemonic safely
-    println!("⚠️ WARNING: Store this mnemonic safely! It is stored in the file: {}", hotkey_path);
+        let mut check = HotkeyCheck { file: file_name.clone(), address: None, encrypted: false, problem: None };
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                check.problem = Some(format!("unreadable: {}", e));
+                checks.push(check);
+                continue;
+            }
+        };
+        if contents.trim().is_empty() {
+            check.problem = Some("file is empty".to_string());
+            checks.push(check);
+            continue;
+        }
 
-    // Call the proxy pallet to add the new account
-    let (api, signer) = setup_substrate_client().await?;
-    
-    // Convert the public key to AccountId32
-    let account_id: AccountId32 = keypair.public().into();
-    let multi_address = MultiAddress::Id(account_id);
+        // The address the file claims: the stored one for named hotkeys, the file name for legacy ones
+        let (claimed, mnemonic) = match file_name.strip_suffix(".json") {
+            Some(stem) => match serde_json::from_str::<HotkeyFile>(&contents) {
+                Ok(hotkey_file) => {
+                    check.encrypted = hotkey_file.encrypted_mnemonic.is_some() && hotkey_file.mnemonic.is_none();
+                    // Unnamed hotkeys are stored under their address
+                    if AccountId32::from_str(stem).is_ok() && stem != hotkey_file.address {
+                        check.problem = Some(format!("stored under {} but holds {}", stem, hotkey_file.address));
+                    }
+                    (hotkey_file.address, hotkey_file.mnemonic)
+                }
+                Err(e) => {
+                    check.problem = Some(format!("not a hotkey file: {}", e));
+                    checks.push(check);
+                    continue;
+                }
+            },
+            None => (file_name.clone(), Some(contents)),
+        };
+
+        match mnemonic {
+            Some(mnemonic) => match hotkey_pair_from_mnemonic(&mnemonic) {
+                Ok(pair) => {
+                    let address = pair.public().to_ss58check();
+                    if address != claimed && check.problem.is_none() {
+                        check.problem = Some(format!("mnemonic derives {}, not {}", address, claimed));
+                    }
+                    check.address = Some(address);
+                }
+                Err(e) => check.problem = Some(format!("invalid mnemonic: {}", e)),
+            },
+            None if check.encrypted => {
+                if AccountId32::from_str(&claimed).is_ok() {
+                    check.address = Some(claimed);
+                } else if check.problem.is_none() {
+                    check.problem = Some(format!("stored address '{}' is not an SS58 address", claimed));
+                }
+            }
+            None => check.problem = Some("holds no mnemonic".to_string()),
+        }
+        checks.push(check);
+    }
 
-    // Create the transaction to add the new account with type NonTransfer
-    let tx = custom_runtime::tx()
-        .proxy()
-        .add_proxy(multi_address, ProxyType::NonTransfer, 0);
+    checks.sort_by(|a, b| a.file.cmp(&b.file));
+    Ok(checks)
+}
 
-    // Sign and submit the transaction using the HIPS key
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
+/// Reports on every stored hotkey and fails when any of them would not load.
+fn handle_verify_keystore(opts: &GlobalOpts) -> Result<(), CliError> {
+    let hotkeys_dir = get_hotkeys_dir();
+    let checks = verify_hotkeys(&hotkeys_dir)?;
+    let failed = checks.iter().filter(|check| check.problem.is_some()).count();
 
-    // Wait for the transaction to be finalized
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
-    println!("✅ Successfully added the hotkey account to the proxy!");
+    if opts.is_json() {
+        print_json(opts, &checks)?;
+    } else if checks.is_empty() {
+        println!("No hotkeys in {}", hotkeys_dir);
+    } else {
+        for check in &checks {
+            match (&check.problem, &check.address) {
+                (Some(problem), _) => println!("❌ {}: {}", check.file, problem),
+                (None, Some(address)) if check.encrypted => println!("🔒 {}: {} (encrypted, not derived)", check.file, address),
+                (None, address) => println!("✅ {}: {}", check.file, address.as_deref().unwrap_or_default()),
+            }
+        }
+        println!("\n{} hotkey file(s) checked, {} with problems", checks.len(), failed);
+    }
 
-    Ok(hotkey_address)
+    if failed > 0 {
+        return Err(format!("{} of {} hotkey files in {} failed to load", failed, checks.len(), hotkeys_dir).into());
+    }
+    Ok(())
 }
 
-/// Generates a 12-word mnemonic phrase.
-fn generate_mnemonic() -> String {
-    let mut entropy = [0u8; 16]; // 128 bits for 12 words
-    rand::thread_rng().fill(&mut entropy);
-
-    let mnemonic = Mnemonic::from_entropy(&entropy).unwrap();
-    mnemonic.to_string() // Use `.to_string()` directly
+/// Lists a keystore directory, explaining how to point the CLI elsewhere if it is missing.
+fn read_keystore_dir(keystore_path: &str) -> Result<fs::ReadDir, CliError> {
+    if !Path::new(keystore_path).is_dir() {
+        return Err(CliError::KeystoreNotFound(keystore_path.to_string()));
+    }
+    Ok(fs::read_dir(keystore_path)?)
 }
 
-/// Creates a hotkey address from a coldkey and mnemonic.
-fn create_hotkey_address(coldkey: &str, mnemonic: &str) -> String {
-    format!("{}_hotkey_{}", coldkey, mnemonic.split_whitespace().next().unwrap()) // Simplified
+/// Label under which the coldkey is listed by `list-wallets`
+const COLDKEY_LABEL: &str = "hips-key";
+
+/// The account `account` names, or the signer's when it is `None`. Only the
+/// latter needs a signer, so lookups of another account work without one.
+fn account_or_signer(opts: &GlobalOpts, account: Option<&str>) -> Result<AccountId32, CliError> {
+    match account {
+        Some(account) => resolve_account(opts, account),
+        None => Ok(AccountId32::from(signer_pair(opts, &opts.signer_spec())?.public().0)),
+    }
 }
 
-async fn setup_substrate_client() -> Result<(OnlineClient<PolkadotConfig>, PairSigner<PolkadotConfig, sr25519::Pair>), Box<dyn std::error::Error>> {
-    let url = env::var("SUBSTRATE_NODE_URL")
-        .unwrap_or_else(|_| "wss://rpc.hippius.network".to_string());
-    
-    println!("🌐 Connecting to Substrate node at: {}", url);
-    let api = OnlineClient::<PolkadotConfig>::from_url(&url).await?;
-    
-    println!("🔑 Preparing transaction signer...");
-    let seed_phrase = env::var("SUBSTRATE_SEED_PHRASE")
-        .unwrap_or_else(|_| "//Alice".to_string());
+/// Resolves an account argument given either as an SS58 address or as the label
+/// of a local wallet (`hips-key` for the coldkey, or a hotkey's file name or name).
+fn resolve_account(opts: &GlobalOpts, input: &str) -> Result<AccountId32, CliError> {
+    if let Ok(account) = AccountId32::from_str(input) {
+        return Ok(account);
+    }
 
-    let pair = sr25519::Pair::from_string(seed_phrase.as_str(), None)
-        .map_err(|e| format!("Failed to create pair: {:?}", e))?;
+    let mut matches = Vec::new();
+    if input == COLDKEY_LABEL {
+        if let Some(address) = find_hips_key(&opts.keystore_path()).ok().flatten().as_deref().and_then(hips_key_address) {
+            matches.push(address);
+        }
+    }
+    let hotkeys = find_hotkeys(&get_hotkeys_dir()).map_err(|e| format!("could not read hotkeys: {}", e))?;
+    matches.extend(
+        hotkeys
+            .into_iter()
+            .filter(|hotkey| hotkey.label == input || hotkey.name == input)
+            .map(|hotkey| hotkey.ss58_address),
+    );
 
-    let signer = PairSigner::new(pair);
+    match matches.as_slice() {
+        [address] => Ok(AccountId32::from_str(address).map_err(|e| format!("invalid address for label '{}': {:?}", input, e))?),
+        [] => Err(CliError::Invalid(format!("'{}' is neither an SS58 address nor a known wallet label", input))),
+        _ => Err(CliError::Invalid(format!("wallet label '{}' is ambiguous; use the SS58 address instead", input))),
+    }
+}
 
-    Ok((api, signer))
+/// Recovers the SS58 address from a keystore file name of the form
+/// `hex("hips") ++ hex(public key)`.
+fn hips_key_address(file_name: &str) -> Option<String> {
+    let public = hex::decode(file_name.get(8..)?).ok()?;
+    let public: [u8; 32] = public.try_into().ok()?;
+    Some(sr25519::Public::from_raw(public).to_ss58check())
 }
 
-async fn handle_request_boot(name: String, plan_id: H256) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Initializing Boot Request For Minner: {}", name);
-    
-    let (api, signer) = setup_substrate_client().await?;
-    
-    println!("📤 Submitting transaction to request boot...");
-    let tx = custom_runtime::tx().compute().request_compute_boot(plan_id);
+/// A named hotkey as stored in `{name}.json`
+#[derive(Serialize, serde::Deserialize)]
+struct HotkeyFile {
+    name: String,
+    address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mnemonic: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encrypted_mnemonic: Option<EncryptedMnemonic>,
+}
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-    
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
-    
-    println!("✅ Successfully requested boot!");
-    println!("📦 Space Name: {}", name);
-    println!("🆔 Plan ID: {:?}", plan_id);
+/// A mnemonic sealed with XChaCha20-Poly1305 under an Argon2id-derived key
+#[derive(Serialize, serde::Deserialize)]
+struct EncryptedMnemonic {
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
 
-    Ok(())
+fn derive_hotkey_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], CliError> {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive key from passphrase: {}", e))?;
+    Ok(key)
 }
 
-async fn handle_request_reboot(name: String, plan_id: H256) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Initializing Boot Request For Minner: {}", name);
-    
-    let (api, signer) = setup_substrate_client().await?;
-    
-    println!("📤 Submitting transaction to request boot...");
-    let tx = custom_runtime::tx().compute().request_compute_reboot(plan_id);
+fn encrypt_mnemonic(mnemonic: &str, passphrase: &str) -> Result<EncryptedMnemonic, CliError> {
+    use chacha20poly1305::aead::{Aead, KeyInit};
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-    
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
-    
-    println!("✅ Successfully requested boot!");
-    println!("📦 Space Name: {}", name);
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 24];
+    rand::thread_rng().fill(&mut salt);
+    rand::thread_rng().fill(&mut nonce);
 
-    Ok(())
+    let cipher = chacha20poly1305::XChaCha20Poly1305::new(&derive_hotkey_key(passphrase, &salt)?.into());
+    let ciphertext = cipher
+        .encrypt(&nonce.into(), mnemonic.as_bytes())
+        .map_err(|_| "Failed to encrypt mnemonic")?;
+
+    Ok(EncryptedMnemonic {
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
 }
 
-async fn handle_request_delete(name: String, plan_id: H256) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Initializing Delete Request For Minner: {}", name);
-    
-    let (api, signer) = setup_substrate_client().await?;
-    
-    println!("📤 Submitting transaction to request delete...");
-    let tx = custom_runtime::tx().compute().request_compute_delete(plan_id);
+fn decrypt_mnemonic(encrypted: &EncryptedMnemonic, passphrase: &str) -> Result<String, CliError> {
+    use chacha20poly1305::aead::{Aead, KeyInit};
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-    
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
-    
-    println!("✅ Successfully requested delete!");
-    println!("📦 Space Name: {}", name);
+    let salt = hex::decode(&encrypted.salt)?;
+    let nonce: [u8; 24] = hex::decode(&encrypted.nonce)?
+        .try_into()
+        .map_err(|_| "Invalid nonce length in hotkey file")?;
+    let ciphertext = hex::decode(&encrypted.ciphertext)?;
 
-    Ok(())
+    let cipher = chacha20poly1305::XChaCha20Poly1305::new(&derive_hotkey_key(passphrase, &salt)?.into());
+    let plaintext = cipher
+        .decrypt(&nonce.into(), ciphertext.as_slice())
+        .map_err(|_| "Wrong passphrase or corrupted hotkey file")?;
+    Ok(String::from_utf8(plaintext)?)
 }
 
-async fn handle_request_stop(name: String, plan_id: H256) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Initializing Stop Request For Minner: {}", name);
-    
-    let (api, signer) = setup_substrate_client().await?;
-    
-    println!("📤 Submitting transaction to request stop...");
-    let tx = custom_runtime::tx().compute().request_compute_stop(plan_id);
-
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-    
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
-    
-    println!("✅ Successfully requested stop!");
-    println!("📦 Space Name: {}", name);
+fn read_hotkey_file(path: &Path) -> Result<HotkeyFile, CliError> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
 
-    Ok(())
+/// Reads the mnemonic from a hotkey file in either format: a named `.json`
+/// hotkey or a legacy plain-text file.
+/// Encrypted hotkeys prompt for their passphrase.
+fn read_hotkey_mnemonic(path: &Path) -> Result<String, CliError> {
+    if path.extension().is_some_and(|ext| ext == "json") {
+        let hotkey_file = read_hotkey_file(path)?;
+        match (hotkey_file.mnemonic, hotkey_file.encrypted_mnemonic) {
+            (Some(mnemonic), _) => Ok(mnemonic),
+            (None, Some(encrypted)) => {
+                let passphrase = rpassword::prompt_password(format!("🔒 Passphrase for hotkey '{}': ", hotkey_file.name))?;
+                decrypt_mnemonic(&encrypted, &passphrase)
+            }
+            (None, None) => Err(format!("Hotkey file {} holds no mnemonic", path.display()).into()),
+        }
+    } else {
+        Ok(fs::read_to_string(path)?)
+    }
 }
 
-async fn handle_purchase_compute_plan(
-    plan_id: H256, 
-    location_id: Option<u32>, 
-    image_name: String, 
-    cloud_init_cid: Option<String>, 
-    _pay_for: Option<String>,
-    miner_id: Option<String>
-) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🛒 Initiating Plan Purchase");
-    
-    let (api, signer) = setup_substrate_client().await?;
-    
-    // Convert inputs to required types
-    let image_name_bytes = image_name.into_bytes();
-    let cloud_init_cid_bytes = cloud_init_cid.map(|cid| cid.into_bytes());
-    
-    // Convert pay_for to AccountId if provided
-    let pay_for_account: Option<_> = None;
+/// Locates a local hotkey by name or address and derives its keypair.
+fn find_hotkey_pair(name_or_address: &str) -> Result<Option<sr25519::Pair>, CliError> {
+    match find_hotkey_path(name_or_address)? {
+        Some(path) => Ok(Some(hotkey_pair_from_mnemonic(&read_hotkey_mnemonic(&path)?)?)),
+        None => Ok(None),
+    }
+}
 
-    // Convert miner_id to bytes if provided
-    let miner_id_bytes = miner_id.map(|id| id.into_bytes());
+/// Locates a local hotkey file by name or address.
+/// Looks for `{name}.json` first, then a legacy address-named file, then
+/// scans for a hotkey whose name or address matches.
+fn find_hotkey_path(name_or_address: &str) -> Result<Option<std::path::PathBuf>, CliError> {
+    let hotkeys_dir = get_hotkeys_dir();
 
-    println!("📤 Submitting transaction to purchase plan...");
-    let tx = custom_runtime::tx()
-        .marketplace()
-        .purchase_plan(
-            plan_id, 
-            location_id, 
-            image_name_bytes, 
-            cloud_init_cid_bytes, 
-            pay_for_account,
-            miner_id_bytes
-        );
+    for candidate in [format!("{}.json", name_or_address), name_or_address.to_string()] {
+        let path = Path::new(&hotkeys_dir).join(candidate);
+        if path.is_file() {
+            return Ok(Some(path));
+        }
+    }
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-    
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
-    
-    println!("✅ Successfully purchased plan!");
-    println!("🆔 Plan ID: {:?}", plan_id);
+    let hotkey = find_hotkeys(&hotkeys_dir)?
+        .into_iter()
+        .find(|hotkey| hotkey.name == name_or_address || hotkey.ss58_address == name_or_address);
+    Ok(hotkey.map(|hotkey| Path::new(&hotkeys_dir).join(hotkey.label)))
+}
 
-    Ok(())
+#[derive(Serialize)]
+struct ProxyOutput {
+    delegate: String,
+    proxy_type: String,
+    /// Announcement delay in blocks
+    delay: u64,
+    /// Name of the local hotkey holding the delegate's key, if any
+    local_hotkey: Option<String>,
 }
 
-async fn handle_storage_command(
-    storage_command: StorageCommand, 
-    file_hash: String,
-    file_name: String,
-    miner_ids: Option<Vec<Vec<u8>>>, // Add this line
-) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🗄️ Initiating Storage Operation");
-    
-    let (api, signer) = setup_substrate_client().await?;
-    
-    match storage_command {
-        StorageCommand::Pin => {
-            // Create FileInput with file hash and VM name
-            let file_input = FileInput {
-                file_hash: file_hash.as_bytes().to_vec(),
-                file_name: file_name.as_bytes().to_vec(),
-            };
+/// Lists the proxies `account` (default: the signer) has delegated to,
+/// marking the delegates whose keys are in the local hotkey keystore.
+async fn handle_list_proxies(opts: &GlobalOpts, account: Option<&str>) -> Result<(), CliError> {
+    let (api, account) = match account {
+        Some(account) => {
+            let account = resolve_account(opts, account)?;
+            (connect_any(opts).await?, account)
+        }
+        None => {
+            let (api, signer) = setup_substrate_client(opts).await?;
+            (api, signer.account_id().clone())
+        }
+    };
+    log::info!("🔍 Querying proxies of {}...", account);
+
+    let proxies = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&custom_runtime::storage().proxy().proxies(account.clone()))
+        .await?
+        .map(|(proxies, _deposit)| proxies.0)
+        .unwrap_or_default();
+
+    let hotkeys = find_hotkeys(&get_hotkeys_dir())?;
+    let outputs: Vec<ProxyOutput> = proxies
+        .iter()
+        .map(|proxy| {
+            let delegate = proxy.delegate.to_string();
+            ProxyOutput {
+                local_hotkey: hotkeys.iter().find(|hotkey| hotkey.ss58_address == delegate).map(|hotkey| hotkey.name.clone()),
+                delegate,
+                proxy_type: format!("{:?}", proxy.proxy_type),
+                delay: proxy.delay,
+            }
+        })
+        .collect();
 
-            println!("📌 Submitting transaction to pin files...");
-            let tx = custom_runtime::tx()
-                .marketplace()
-                .storage_request(vec![file_input], miner_ids); // Update this line
+    if opts.is_json() {
+        return print_json(opts, &outputs);
+    }
 
-            let progress = api
-                .tx()
-                .sign_and_submit_then_watch_default(&tx, &signer)
-                .await?;
-            
-            println!("⏳ Waiting for transaction to be finalized...");
-            let _ = progress.wait_for_finalized_success().await?;
-            
-            println!("✅ Successfully pinned files!");
-        },
-        StorageCommand::Unpin => {
-            println!("🔓 Submitting transaction to unpin file...");
-            let tx = custom_runtime::tx()
-                .marketplace()
-                .storage_unpin_request(file_hash.clone().into());
+    if outputs.is_empty() {
+        println!("ℹ️ {} has no proxies.", account);
+        return Ok(());
+    }
 
-            let progress = api
-                .tx()
-                .sign_and_submit_then_watch_default(&tx, &signer)
-                .await?;
-            
-            println!("⏳ Waiting for transaction to be finalized...");
-            let _ = progress.wait_for_finalized_success().await?;
-            
-            println!("✅ Successfully unpinned file!");
+    println!("🔢 Total Proxies: {}", outputs.len());
+    for proxy in &outputs {
+        println!("\n👤 Delegate: {}", proxy.delegate);
+        println!("  Proxy Type: {}", proxy.proxy_type);
+        println!("  Delay: {} blocks", proxy.delay);
+        match &proxy.local_hotkey {
+            Some(name) => println!("  Local Hotkey: {}", name),
+            None => println!("  Local Hotkey: none (key not in this keystore)"),
         }
     }
 
     Ok(())
 }
 
-async fn handle_list_images() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🖼️  Fetching Available OS Disk Images...");
-    
-    let (api, _) = setup_substrate_client().await?;
-    
-    // Build a dynamic storage query for OS disk image URLs
-    let storage_query = subxt::dynamic::storage("Marketplace", "OSDiskImageUrls", vec![]);
-    
-    // Fetch storage entries
-    let mut results = api.storage().at_latest().await?.iter(storage_query).await?;
-    
-    let mut image_list = Vec::new();
-    
-    // Iterate through results
-    while let Some(Ok(kv)) = results.next().await {
-        // Convert keys and values to bytes
-        let os_name_bytes = kv.key_bytes[kv.key_bytes.len() - 32..].to_vec();
-        
-        // Attempt to decode the value into a Vec<u8>
-        let url_bytes: Vec<u8> = kv.value.as_type()?;
-        
-        // Convert bytes to strings
-        let os_name = String::from_utf8_lossy(&os_name_bytes).into_owned();
-        let url = String::from_utf8_lossy(&url_bytes).into_owned();
-        
-        // Optional: Add a filter to ensure valid URLs
-        if !os_name.is_empty() && !url.is_empty() {
-            image_list.push((os_name, url));
+/// Revokes a hotkey's proxy on the signer's account, then deletes its
+/// keystore file. A hotkey that was never registered as a proxy is only
+/// deleted, with a warning.
+async fn handle_remove_hotkey(opts: &GlobalOpts, hotkey: &str) -> Result<(), CliError> {
+    let hotkeys_dir = get_hotkeys_dir();
+    let path = find_hotkey_path(hotkey)?
+        .ok_or_else(|| CliError::Invalid(format!("No hotkey named '{}' in {}", hotkey, hotkeys_dir)))?;
+    // Listed hotkeys carry their address, so encrypted ones need no passphrase here
+    let label = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let info = find_hotkeys(&hotkeys_dir)?
+        .into_iter()
+        .find(|info| info.label == label)
+        .ok_or_else(|| format!("Hotkey file {} could not be read", path.display()))?;
+    let hotkey_account = AccountId32::from_str(&info.ss58_address)
+        .map_err(|e| format!("Hotkey {} has an invalid address: {}", info.name, e))?;
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+
+    let proxies = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&custom_runtime::storage().proxy().proxies(signer.account_id().clone()))
+        .await?
+        .map(|(proxies, _deposit)| proxies.0)
+        .unwrap_or_default();
+    let definitions: Vec<_> = proxies.into_iter().filter(|proxy| proxy.delegate == hotkey_account).collect();
+
+    let prompt = if definitions.is_empty() {
+        log::warn!("⚠️ Hotkey {} is not a proxy of {}; only its keystore file will be deleted.", info.ss58_address, signer.account_id());
+        format!("Delete hotkey '{}' ({})?", info.name, info.ss58_address)
+    } else {
+        format!("Revoke the proxy of hotkey '{}' ({}) and delete it?", info.name, info.ss58_address)
+    };
+    if !opts.dry_run && !confirm(opts, &prompt)? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
+
+    for definition in definitions {
+        log::info!("📤 Removing {:?} proxy of {}...", definition.proxy_type, info.ss58_address);
+        let tx = custom_runtime::tx()
+            .proxy()
+            .remove_proxy(MultiAddress::Id(hotkey_account.clone()), definition.proxy_type, definition.delay);
+        if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+            return Ok(());
         }
     }
-    
-    if image_list.is_empty() {
-        println!("No OS disk images found in the marketplace.");
+    if opts.dry_run {
+        log::info!("🧪 Dry run, keeping the keystore file {}", path.display());
         return Ok(());
     }
-    
-    println!("Available OS Disk Images:");
-    println!("--------------------");
-    for (os_name, url) in image_list {
-        println!("OS: {:<10} | URL: {}", os_name, url);
+
+    fs::remove_file(&path)?;
+    // Legacy address-named hotkeys may have a `<hotkey>.json` name sidecar
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(".json");
+    if Path::new(&sidecar).is_file() {
+        fs::remove_file(&sidecar)?;
     }
-    
+
+    log::info!("✅ Removed hotkey '{}' ({})", info.name, info.ss58_address);
     Ok(())
 }
 
-/// Query free credits for signer's account
-async fn handle_get_credits() -> Result<(), Box<dyn std::error::Error>> {
-    println!("💰 Querying Free Credits...");
+/// All hotkeys of a keystore, as written by `wallet export`
+#[derive(Serialize, serde::Deserialize)]
+struct WalletBackup {
+    version: u32,
+    hotkeys: Vec<HotkeyFile>,
+}
 
-    let (api, signer) = setup_substrate_client().await?;
+const WALLET_BACKUP_VERSION: u32 = 1;
 
-    // Use signer's account ID directly
-    let target_account = subxt::dynamic::Value::from_bytes(&signer.account_id().encode());
+/// Writes every hotkey into one backup file, readable only by the user. Without
+/// `--encrypt` plaintext mnemonics stay plaintext and encrypted ones keep their
+/// own passphrase; with it, all of them are sealed under one backup passphrase.
+fn handle_wallet_export(opts: &GlobalOpts, out: &str, encrypt: bool) -> Result<(), CliError> {
+    if Path::new(out).exists() {
+        return Err(CliError::Invalid(format!("{} already exists; choose another --out", out)));
+    }
+    let hotkeys_dir = get_hotkeys_dir();
+    let infos = find_hotkeys(&hotkeys_dir)?;
+    if infos.is_empty() {
+        return Err(format!("No hotkeys in {} to export", hotkeys_dir).into());
+    }
 
-    // Build a dynamic storage query for free credits
-    let storage_query = subxt::dynamic::storage("Credits", "FreeCredits", vec![target_account]);
+    let passphrase = if encrypt {
+        let passphrase = rpassword::prompt_password("🔒 Passphrase for the backup: ")?;
+        if passphrase.is_empty() {
+            return Err("The passphrase must not be empty".into());
+        }
+        if rpassword::prompt_password("🔒 Repeat passphrase: ")? != passphrase {
+            return Err("Passphrases do not match".into());
+        }
+        Some(passphrase)
+    } else {
+        None
+    };
 
-    // Fetch the credits value
-    let credits_result = api.storage().at_latest().await?.fetch(&storage_query).await;
+    let mut hotkeys = Vec::new();
+    for info in &infos {
+        let path = Path::new(&hotkeys_dir).join(&info.label);
+        let hotkey = match (&passphrase, info.label.ends_with(".json")) {
+            // Kept sealed under the hotkey's own passphrase
+            (None, true) => read_hotkey_file(&path)?,
+            (None, false) => HotkeyFile {
+                name: info.name.clone(),
+                address: info.ss58_address.clone(),
+                mnemonic: Some(fs::read_to_string(&path)?.trim().to_string()),
+                encrypted_mnemonic: None,
+            },
+            (Some(passphrase), _) => HotkeyFile {
+                name: info.name.clone(),
+                address: info.ss58_address.clone(),
+                mnemonic: None,
+                encrypted_mnemonic: Some(encrypt_mnemonic(read_hotkey_mnemonic(&path)?.trim(), passphrase)?),
+            },
+        };
+        hotkeys.push(hotkey);
+    }
 
-    match credits_result {
-        Ok(Some(credits_value)) => {
-            // Convert credits value to u128
-            let credits: u128 = credits_value.as_type().unwrap_or(0);
+    let backup = WalletBackup { version: WALLET_BACKUP_VERSION, hotkeys };
+    write_secret_file(Path::new(out), serde_json::to_string_pretty(&backup)?.as_bytes())?;
 
-            println!("✅ Free Credits:");
-            println!("🔢 Amount: {}", credits);
-        },
-        Ok(None) => {
-            println!("❌ No credits found for the account.");
-        },
-        Err(e) => {
-            eprintln!("🚨 Error querying credits: {}", e);
-            return Err(e.into());
-        }
+    let plaintext = backup.hotkeys.iter().filter(|hotkey| hotkey.mnemonic.is_some()).count();
+    if plaintext > 0 {
+        log::warn!("⚠️ {} mnemonic(s) are stored in the clear in {}; pass --encrypt to seal them", plaintext, out);
     }
-
+    if opts.is_json() {
+        return print_json(opts, &backup.hotkeys.iter().map(|hotkey| &hotkey.address).collect::<Vec<_>>());
+    }
+    println!("✅ Exported {} hotkey(s) to {}", backup.hotkeys.len(), out);
     Ok(())
 }
 
-async fn handle_insert_key(seed_phrase: String, public_key: String) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔑 Inserting key to local node...");
+/// Restores a `wallet export` backup. Every entry is checked first, and nothing
+/// is written if a name is already taken by a different key; hotkeys that are
+/// already present are skipped. Encrypted entries stay encrypted under the
+/// passphrase they were exported with.
+fn handle_wallet_import(opts: &GlobalOpts, file: &str) -> Result<(), CliError> {
+    let contents = fs::read_to_string(file).map_err(|e| format!("could not read {}: {}", file, e))?;
+    let backup: WalletBackup = serde_json::from_str(&contents)
+        .map_err(|e| CliError::Invalid(format!("{} is not a wallet backup: {}", file, e)))?;
+    if backup.version != WALLET_BACKUP_VERSION {
+        return Err(CliError::Invalid(format!("unsupported wallet backup version {}", backup.version)));
+    }
 
-    // Prepare the JSON-RPC request payload
-    let client = reqwest::Client::new();
-    let payload = serde_json::json!({
-        "jsonrpc": "2.0",
-        "id": 1,
-        "method": "author_insertKey",
-        "params": [
-            "hips",  // Hardcoded key type
-            seed_phrase,
-            public_key
-        ]
-    });
+    let hotkeys_dir = get_hotkeys_dir();
+    let existing = find_hotkeys(&hotkeys_dir)?;
+    let mut to_write = Vec::new();
+    let mut conflicts = Vec::new();
+    for hotkey in backup.hotkeys {
+        validate_hotkey_name(&hotkey.name)?;
+        match (&hotkey.mnemonic, &hotkey.encrypted_mnemonic) {
+            (Some(mnemonic), _) => {
+                let address = hotkey_pair_from_mnemonic(mnemonic)?.public().to_ss58check();
+                if address != hotkey.address {
+                    return Err(CliError::Invalid(format!("hotkey '{}' in the backup derives {}, not {}", hotkey.name, address, hotkey.address)));
+                }
+            }
+            (None, Some(_)) => {}
+            (None, None) => return Err(CliError::Invalid(format!("hotkey '{}' in the backup has no mnemonic", hotkey.name))),
+        }
 
-    // Send the request to the local node
-    let response = client
-        .post("https://rpc.hippius.network")
-        .header("Content-Type", "application/json")
-        .json(&payload)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request: {}", e))?;
+        if let Some(present) = existing.iter().find(|present| present.ss58_address == hotkey.address) {
+            log::info!("⏭️  {} is already in the keystore as '{}'", hotkey.address, present.name);
+            continue;
+        }
+        if Path::new(&hotkeys_dir).join(format!("{}.json", hotkey.name)).exists() {
+            conflicts.push(format!("'{}' already holds a different key", hotkey.name));
+            continue;
+        }
+        if to_write.iter().any(|other: &HotkeyFile| other.name == hotkey.name) {
+            conflicts.push(format!("'{}' appears twice in the backup", hotkey.name));
+            continue;
+        }
+        to_write.push(hotkey);
+    }
+    if !conflicts.is_empty() {
+        return Err(CliError::Invalid(format!("nothing was imported: {}", conflicts.join("; "))));
+    }
 
-    // Check the response
-    if response.status().is_success() {
-        let response_text = response.text().await?;
-        println!("✅ Key insertion response: {}", response_text);
-        println!("🔑 Key inserted successfully!");
-    } else {
-        return Err(format!("Failed to insert key. Status: {}", response.status()).into());
+    fs::create_dir_all(&hotkeys_dir)?;
+    for hotkey in &to_write {
+        let path = Path::new(&hotkeys_dir).join(format!("{}.json", hotkey.name));
+        write_secret_file(&path, serde_json::to_string_pretty(hotkey)?.as_bytes())?;
+        log::info!("💾 Restored '{}' ({}) to {}", hotkey.name, hotkey.address, path.display());
     }
 
+    if opts.is_json() {
+        return print_json(opts, &to_write.iter().map(|hotkey| &hotkey.address).collect::<Vec<_>>());
+    }
+    println!("✅ Imported {} hotkey(s)", to_write.len());
     Ok(())
 }
 
+/// Prints a hotkey's mnemonic so it can be moved to another machine. The
+/// user has to type the hotkey's address to confirm; `--yes` only skips
+/// that together with `--i-understand-the-risk`.
+fn handle_export_hotkey(opts: &GlobalOpts, hotkey: &str, i_understand_the_risk: bool) -> Result<(), CliError> {
+    if opts.yes && !i_understand_the_risk {
+        return Err(CliError::Invalid("--yes only skips the export confirmation together with --i-understand-the-risk".to_string()));
+    }
 
-/// Query and print node information where the signer is the owner
-async fn handle_query_my_node() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔍 Querying Node Registration for Your Node...");
+    let path = find_hotkey_path(hotkey)?
+        .ok_or_else(|| CliError::Invalid(format!("No hotkey named '{}' in {}", hotkey, get_hotkeys_dir())))?;
+    let mnemonic = read_hotkey_mnemonic(&path)?.trim().to_string();
+    let pair = hotkey_pair_from_mnemonic(&mnemonic)?;
+    let address = pair.public().to_ss58check();
+
+    if !(opts.yes && i_understand_the_risk) {
+        if !std::io::stdin().is_terminal() {
+            return Err(CliError::Invalid(
+                "Not exporting: stdin is not a terminal (pass --yes --i-understand-the-risk to export anyway)".to_string(),
+            ));
+        }
+        log::warn!("⚠️ WARNING: Anyone who sees the mnemonic controls this hotkey.");
+        print!("❓ Type the hotkey address ({}) to print its mnemonic: ", address);
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if answer.trim() != address {
+            log::info!("🚫 Cancelled: the address did not match");
+            return Ok(());
+        }
+    }
 
-    let (api, signer) = setup_substrate_client().await?;
+    if opts.is_json() {
+        return print_json(opts, &GeneratedKey {
+            address,
+            scheme: KeyScheme::Sr25519,
+            public_key_hex: hex::encode(pair.public().0),
+            mnemonic: Some(mnemonic),
+            keystore_path: path.display().to_string(),
+            proxy_tx_hash: None,
+        });
+    }
 
-    // Get the signer's account ID
-    let signer_account_id = signer.account_id();
+    println!("📍 Hotkey Address: {}", address);
+    println!("📝 Mnemonic: {}", mnemonic);
 
-    // Build a dynamic storage query for the NodeRegistration map
-    let storage_query = subxt::dynamic::storage("Registration", "NodeRegistration", vec![]);
+    Ok(())
+}
 
-    // Fetch all entries from the NodeRegistration map
-    let mut results = api.storage().at_latest().await?.iter(storage_query).await?;
+/// Metadata kept next to a hotkey file as `<hotkey>.json`
+#[derive(serde::Deserialize)]
+struct HotkeyMetadata {
+    name: String,
+}
 
-    let mut found = false;
+/// Reads the human name from a hotkey's `.json` sidecar, if it has a usable one.
+fn read_hotkey_name(hotkey_path: &Path) -> Option<String> {
+    let mut sidecar = hotkey_path.as_os_str().to_owned();
+    sidecar.push(".json");
+    let metadata: HotkeyMetadata = serde_json::from_str(&fs::read_to_string(sidecar).ok()?).ok()?;
+    Some(metadata.name).filter(|name| !name.trim().is_empty())
+}
 
-    // Iterate through the results
-    while let Some(Ok(kv)) = results.next().await {
-        // Decode the value into the expected type
-        let node_info: Option<NodeInfo<u32, AccountId32>> = kv.value.as_type()?;
-
-        if let Some(node_info) = node_info {
-            // Check if the owner matches the signer's account ID
-            if node_info.owner == *signer_account_id {
-                println!("✅ Your Node Information:");
-                println!("------------------------");
-
-                // Convert Vec<u8> fields to strings
-                let node_id = String::from_utf8(node_info.node_id).unwrap_or_else(|_| "Invalid UTF-8".to_string());
-                let node_type = node_info.node_type;
-                let ipfs_node_id = node_info.ipfs_node_id
-                    .map(|id| String::from_utf8(id).unwrap_or_else(|_| "Invalid UTF-8".to_string()))
-                    .unwrap_or_else(|| "None".to_string());
-                let status = node_info.status;
-
-                // Convert AccountId32 to SS58 address
-                let owner= node_info.owner;
-                println!("Node ID: {}", node_id);
-                println!("Node Type: {:?}", node_type);
-                println!("IPFS Node ID: {}", ipfs_node_id);
-                println!("Status: {:?}", status);
-                println!("Registered At: {}", node_info.registered_at);
-                println!("Owner: {:?}", owner);
-                println!("------------------------");
-
-                found = true;
-                break; // Exit the loop once the node is found
-            }
-        }
+/// Shortens an SS58 address to its first six and last four characters.
+fn short_address(address: &str) -> String {
+    match (address.get(..6), address.get(address.len().saturating_sub(4)..)) {
+        (Some(head), Some(tail)) if address.len() > 10 => format!("{}...{}", head, tail),
+        _ => address.to_string(),
     }
+}
 
-    if !found {
-        println!("❌ Your node is not registered yet.");
-    }
+/// Derives a hotkey's sr25519 keypair from its stored BIP39 mnemonic.
+fn hotkey_pair_from_mnemonic(mnemonic: &str) -> Result<sr25519::Pair, CliError> {
+    Ok(sr25519::Pair::from_seed(&mnemonic_seed(mnemonic)?))
+}
 
-    Ok(())
+/// The 32-byte key seed of a BIP39 mnemonic: the first half of its BIP39
+/// seed, as used for both hotkeys and `generate-keys`.
+fn mnemonic_seed(mnemonic: &str) -> Result<[u8; 32], CliError> {
+    let mnemonic = Mnemonic::parse_in_normalized(Language::English, mnemonic.trim())?;
+    let seed = mnemonic.to_seed("");
+    Ok(seed[..32].try_into().map_err(|_| "Seed slice has incorrect length")?)
 }
 
-/// Fetch and display compute-related information
-async fn handle_compute_infos() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔍 Querying Node Registration for Your Node...");
+/// The lengths BIP39 allows for a mnemonic
+const MNEMONIC_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+fn parse_word_count(s: &str) -> Result<usize, String> {
+    s.parse()
+        .ok()
+        .filter(|count| MNEMONIC_WORD_COUNTS.contains(count))
+        .ok_or_else(|| format!("'{}' is not a mnemonic length; use one of 12, 15, 18, 21 or 24", s))
+}
 
-    let (api, signer) = setup_substrate_client().await?;
+async fn create_hotkey(
+    opts: &GlobalOpts,
+    name: Option<String>,
+    encrypt: bool,
+    word_count: usize,
+    proxy_type: ProxyType,
+    delay: u64,
+) -> Result<GeneratedKey, CliError> {
+    // Generate a new mnemonic
+    let mnemonic = generate_mnemonic(word_count);
+    
+    // Generate keypair from mnemonic
+    let keypair = hotkey_pair_from_mnemonic(&mnemonic)?;
 
-    // Get the signer's account ID
-    let signer_account_id = signer.account_id();
+    // Create hotkey address
+    let hotkey_address = keypair.public().to_ss58check();
 
-    // Build a dynamic storage query for the NodeRegistration map
-    let storage_query = subxt::dynamic::storage("Registration", "NodeRegistration", vec![]);
+    let hotkey_path = write_hotkey_file(&get_hotkeys_dir(), name, &hotkey_address, &mnemonic, encrypt)?;
 
-    // Fetch all entries from the NodeRegistration map
-    let mut results = api.storage().at_latest().await?.iter(storage_query).await?;
+    // In JSON mode the mnemonic is only emitted once, inside the JSON document
+    if opts.is_json() {
+        log::warn!("⚠️ WARNING: The JSON output contains the hotkey mnemonic. Treat it as a secret!");
+    } else {
+        println!("📝 Mnemonic: {}", mnemonic);
+    }
 
-    let mut found = false;
+    // Warning about storing the mnemonic safely
+    log::warn!("⚠️ WARNING: Store this mnemonic safely! It is stored in the file: {}", hotkey_path);
+
+    let proxy_tx_hash = add_hotkey_proxy(opts, &keypair, proxy_type, delay).await?;
+
+    Ok(GeneratedKey {
+        address: hotkey_address,
+        scheme: KeyScheme::Sr25519,
+        public_key_hex: hex::encode(keypair.public().0),
+        mnemonic: Some(mnemonic),
+        keystore_path: hotkey_path,
+        proxy_tx_hash,
+    })
+}
 
-    // Iterate through the results
-    while let Some(Ok(kv)) = results.next().await {
-        // Decode the value into the expected type
-        let node_info: Option<NodeInfo<u32, AccountId32>> = kv.value.as_type()?;
-
-        if let Some(node_info) = node_info {
-            // Check if the owner matches the signer's account ID
-            if node_info.owner == *signer_account_id {
-                println!("✅ Your Node Information:");
-                println!("------------------------");
-
-                // Convert Vec<u8> fields to strings
-                let node_id = String::from_utf8(node_info.node_id).unwrap_or_else(|_| "Invalid UTF-8".to_string());
-                let node_type = node_info.node_type;
-                let ipfs_node_id = node_info.ipfs_node_id
-                    .map(|id| String::from_utf8(id).unwrap_or_else(|_| "Invalid UTF-8".to_string()))
-                    .unwrap_or_else(|| "None".to_string());
-                let status = node_info.status;
-
-                // Convert AccountId32 to SS58 address
-                let owner= node_info.owner;
-                println!("Node ID: {}", node_id);
-                println!("Node Type: {:?}", node_type);
-                println!("IPFS Node ID: {}", ipfs_node_id);
-                println!("Status: {:?}", status);
-                println!("Registered At: {}", node_info.registered_at);
-                println!("Owner: {:?}", owner);
-                println!("------------------------");
-
-                found = true;
-                break; // Exit the loop once the node is found
+/// Stores an existing mnemonic as a hotkey, in the same owner-only file as
+/// `create_hotkey`. The mnemonic is read from `mnemonic_file`, else stdin,
+/// and is never echoed back. With `proxy` the hotkey is also added as a
+/// proxy of that type and delay.
+async fn import_hotkey(
+    opts: &GlobalOpts,
+    name: Option<String>,
+    mnemonic_file: Option<&str>,
+    encrypt: bool,
+    proxy: Option<(ProxyType, u64)>,
+) -> Result<GeneratedKey, CliError> {
+    let mnemonic = match mnemonic_file {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("could not read mnemonic file {}: {}", path, e))?,
+        None => {
+            if std::io::stdin().is_terminal() {
+                log::info!("📝 Enter the mnemonic, then press Ctrl-D:");
             }
+            String::from_utf8(read_message(None)?).map_err(|_| "The mnemonic is not valid UTF-8")?
         }
-    }
+    };
+    // Normalize whitespace so the stored phrase matches a generated one
+    let mnemonic = mnemonic.split_whitespace().collect::<Vec<_>>().join(" ");
 
-    if !found {
-        println!("❌ Your node is not registered yet.");
-    }
+    let keypair = hotkey_pair_from_mnemonic(&mnemonic)
+        .map_err(|e| CliError::Invalid(format!("not a valid BIP39 mnemonic: {}", e)))?;
+    let hotkey_address = keypair.public().to_ss58check();
 
-    println!("🖥️ Fetching Compute Information...");
+    let hotkey_path = write_hotkey_file(&get_hotkeys_dir(), name, &hotkey_address, &mnemonic, encrypt)?;
+    log::info!("💾 Stored the hotkey in {}", hotkey_path);
 
-    // Fetch libvirt version
-    let libvirt_version = Command::new("libvirtd")
-        .arg("--version")
-        .output()
-        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
-        .unwrap_or_else(|_| "Not installed".to_string());
-    println!("📦 Libvirt Version: {}", libvirt_version);
+    let proxy_tx_hash = match proxy {
+        Some((proxy_type, delay)) => add_hotkey_proxy(opts, &keypair, proxy_type, delay).await?,
+        None => None,
+    };
+
+    Ok(GeneratedKey {
+        address: hotkey_address,
+        scheme: KeyScheme::Sr25519,
+        public_key_hex: hex::encode(keypair.public().0),
+        mnemonic: None,
+        keystore_path: hotkey_path,
+        proxy_tx_hash,
+    })
+}
 
+/// Hotkey names become file names, so they may not escape the hotkeys directory.
+fn validate_hotkey_name(name: &str) -> Result<(), CliError> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(format!("Invalid hotkey name '{}': it must be non-empty, not start with '.', and contain no path separators", name).into());
+    }
     Ok(())
 }
 
-/// Fetch and display storage-related information
-async fn handle_storage_infos() -> Result<(), Box<dyn std::error::Error>> {
+/// Saves a hotkey to `hotkeys_dir` as `{name}.json` (the name defaults to the
+/// address), optionally encrypting the mnemonic, and returns the file path.
+/// The file is owner-only, and an existing hotkey of the same name is never overwritten.
+fn write_hotkey_file(hotkeys_dir: &str, name: Option<String>, hotkey_address: &str, mnemonic: &str, encrypt: bool) -> Result<String, CliError> {
+    // Ensure hotkeys directory exists
+    fs::create_dir_all(hotkeys_dir)?;
+
+    // Save the hotkey to keystore as `{name}.json`
+    let name = name.unwrap_or_else(|| hotkey_address.to_string());
+    validate_hotkey_name(&name)?;
+    let hotkey_path = format!("{}/{}.json", hotkeys_dir, name);
+    if Path::new(&hotkey_path).exists() {
+        return Err(format!("A hotkey named '{}' already exists at {}", name, hotkey_path).into());
+    }
+    let (plain_mnemonic, encrypted_mnemonic) = if encrypt {
+        let passphrase = rpassword::prompt_password("🔒 Passphrase for the new hotkey: ")?;
+        if passphrase.is_empty() {
+            return Err("The passphrase must not be empty".into());
+        }
+        if rpassword::prompt_password("🔒 Repeat passphrase: ")? != passphrase {
+            return Err("Passphrases do not match".into());
+        }
+        (None, Some(encrypt_mnemonic(mnemonic, &passphrase)?))
+    } else {
+        (Some(mnemonic.to_string()), None)
+    };
+    let hotkey_file = HotkeyFile {
+        name,
+        address: hotkey_address.to_string(),
+        mnemonic: plain_mnemonic,
+        encrypted_mnemonic,
+    };
+    write_secret_file(Path::new(&hotkey_path), serde_json::to_string_pretty(&hotkey_file)?.as_bytes())?;
 
-    println!("🔍 Querying Node Registration for Your Node...");
+    Ok(hotkey_path)
+}
 
-    let (api, signer) = setup_substrate_client().await?;
+/// Adds the hotkey as a proxy of the signer, returning the extrinsic hash
+/// (`None` on a dry run).
+async fn add_hotkey_proxy(opts: &GlobalOpts, keypair: &sr25519::Pair, proxy_type: ProxyType, delay: u64) -> Result<Option<String>, CliError> {
+    // Call the proxy pallet to add the new account
+    let (api, signer) = setup_substrate_client(opts).await?;
+    
+    // Convert the public key to AccountId32
+    let account_id: AccountId32 = keypair.public().into();
+    let multi_address = MultiAddress::Id(account_id);
 
-    // Get the signer's account ID
-    let signer_account_id = signer.account_id();
+    let tx = custom_runtime::tx()
+        .proxy()
+        .add_proxy(multi_address, proxy_type, delay);
 
-    // Build a dynamic storage query for the NodeRegistration map
-    let storage_query = subxt::dynamic::storage("Registration", "NodeRegistration", vec![]);
+    // Sign and submit the transaction using the HIPS key
+    let events = submit_and_wait(opts, &api, &tx, &signer).await?;
+    if events.is_some() {
+        opts.status("✅ Successfully added the hotkey account to the proxy!");
+    }
 
-    // Fetch all entries from the NodeRegistration map
-    let mut results = api.storage().at_latest().await?.iter(storage_query).await?;
+    Ok(events.map(|events| format!("{:?}", events.extrinsic_hash())))
+}
 
-    let mut found = false;
+/// Generates a mnemonic phrase of `word_count` words, one of
+/// `MNEMONIC_WORD_COUNTS`.
+fn generate_mnemonic(word_count: usize) -> String {
+    // Each word holds 11 bits, a 33rd of which are checksum: 12 words carry 128 bits of entropy, 24 carry 256
+    let mut entropy = vec![0u8; word_count * 11 * 32 / 33 / 8];
+    rand::thread_rng().fill(&mut entropy[..]);
 
-    // Iterate through the results
-    while let Some(Ok(kv)) = results.next().await {
-        // Decode the value into the expected type
-        let node_info: Option<NodeInfo<u32, AccountId32>> = kv.value.as_type()?;
-
-        if let Some(node_info) = node_info {
-            // Check if the owner matches the signer's account ID
-            if node_info.owner == *signer_account_id {
-                println!("✅ Your Node Information:");
-                println!("------------------------");
-
-                // Convert Vec<u8> fields to strings
-                let node_id = String::from_utf8(node_info.node_id).unwrap_or_else(|_| "Invalid UTF-8".to_string());
-                let node_type = node_info.node_type;
-                let ipfs_node_id = node_info.ipfs_node_id
-                    .map(|id| String::from_utf8(id).unwrap_or_else(|_| "Invalid UTF-8".to_string()))
-                    .unwrap_or_else(|| "None".to_string());
-                let status = node_info.status;
-
-                // Convert AccountId32 to SS58 address
-                let owner= node_info.owner;
-                println!("Node ID: {}", node_id);
-                println!("Node Type: {:?}", node_type);
-                println!("IPFS Node ID: {}", ipfs_node_id);
-                println!("Status: {:?}", status);
-                println!("Registered At: {}", node_info.registered_at);
-                println!("Owner: {:?}", owner);
-                println!("------------------------");
-
-                found = true;
-                break; // Exit the loop once the node is found
-            }
+    let mnemonic = Mnemonic::from_entropy(&entropy).unwrap();
+    mnemonic.to_string() // Use `.to_string()` directly
+}
+
+/// Connects and resolves the `--signer`. Under `proxy call` the proxy's hotkey
+/// signs instead, on the real account's behalf.
+async fn setup_substrate_client(opts: &GlobalOpts) -> Result<(OnlineClient<PolkadotConfig>, PairSigner<PolkadotConfig, sr25519::Pair>), CliError> {
+    // Resolve the signer first so a missing one fails before any network round trip
+    let signer = match &opts.proxy {
+        Some(proxy) => {
+            let signer = resolve_signer(opts, &SignerSpec::Hotkey(proxy.hotkey.clone()))?;
+            opts.status(format!("🔑 Signing with hotkey {} as a proxy of {}", signer.account_id(), proxy.real));
+            signer
         }
+        None => resolve_signer(opts, &opts.signer_spec())?,
+    };
+    let api = connect_any(opts).await?;
+    Ok((api, signer))
+}
+
+/// Where the signing key comes from, as given to `--signer`
+#[derive(Clone, Debug, PartialEq)]
+enum SignerSpec {
+    /// The configured signer (`--seed-file`, SUBSTRATE_SEED_PHRASE, the profile or `--dev`)
+    EnvColdkey,
+    /// A hotkey from the keystore, by name or address
+    Hotkey(String),
+    /// A file holding a mnemonic, secret URI or raw 32-byte seed
+    SeedFile(String),
+    /// A mnemonic or secret URI given inline
+    Mnemonic(String),
+}
+
+fn parse_signer_spec(s: &str) -> Result<SignerSpec, String> {
+    match s.split_once(':') {
+        None if s == "coldkey" => Ok(SignerSpec::EnvColdkey),
+        Some(("hotkey", name)) if !name.is_empty() => Ok(SignerSpec::Hotkey(name.to_string())),
+        Some(("seed-file", path)) if !path.is_empty() => Ok(SignerSpec::SeedFile(path.to_string())),
+        Some(("mnemonic", phrase)) if !phrase.trim().is_empty() => Ok(SignerSpec::Mnemonic(phrase.trim().to_string())),
+        _ => Err("expected coldkey, hotkey:<name or address>, seed-file:<path> or mnemonic:<phrase>".to_string()),
     }
+}
 
-    if !found {
-        println!("❌ Your node is not registered yet.");
+/// Reads a mnemonic or secret URI from `path`, or the raw 32-byte seed that
+/// `generate-keys` writes, as a hex secret URI.
+fn read_seed_file(path: &str) -> Result<String, CliError> {
+    let contents = fs::read(path).map_err(|e| format!("could not read seed file {}: {}", path, e))?;
+    match String::from_utf8(contents) {
+        Ok(text) => Ok(text.trim().to_string()),
+        Err(e) if e.as_bytes().len() == 32 => Ok(format!("0x{}", hex::encode(e.as_bytes()))),
+        Err(_) => Err(format!("seed file {} is neither text nor a 32-byte seed", path).into()),
     }
+}
 
+/// The key pair `spec` refers to
+fn signer_pair(opts: &GlobalOpts, spec: &SignerSpec) -> Result<sr25519::Pair, CliError> {
+    let suri = match spec {
+        SignerSpec::Hotkey(name) => {
+            return find_hotkey_pair(name)?
+                .ok_or_else(|| CliError::Invalid(format!("Hotkey '{}' not found in {}", name, get_hotkeys_dir())));
+        }
+        SignerSpec::EnvColdkey => opts.signer_suri()?,
+        SignerSpec::SeedFile(path) => read_seed_file(path)?,
+        SignerSpec::Mnemonic(phrase) => phrase.clone(),
+    };
+    Ok(sr25519::Pair::from_string(&suri, None).map_err(|e| format!("Failed to create pair: {:?}", e))?)
+}
 
-    println!("💽 Fetching Storage Information...");
+/// The transaction signer for `spec`
+fn resolve_signer(opts: &GlobalOpts, spec: &SignerSpec) -> Result<PairSigner<PolkadotConfig, sr25519::Pair>, CliError> {
+    let pair = signer_pair(opts, spec)?;
+    opts.status("🔑 Preparing transaction signer...");
+    Ok(PairSigner::new(pair))
+}
 
-    // Fetch IPFS version
-    let ipfs_version = Command::new("ipfs")
-        .arg("--version")
-        .output()
-        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
-        .unwrap_or_else(|_| "Not installed".to_string());
-    println!("📦 IPFS Version of your node is : {}", ipfs_version);
+/// A durable record of a finalized transaction, written under `--receipt-dir`
+#[derive(Serialize)]
+struct TxReceipt {
+    command: String,
+    call_hex: String,
+    tx_hash: String,
+    block_hash: String,
+    timestamp: u64,
+    signer: String,
+    events: Vec<String>,
+}
 
+/// Signs and submits `tx`, waits up to `--finalize-timeout` for it to be
+/// finalized (or only included under `--wait-for inblock`) successfully and, when `--receipt-dir` is set, records a receipt
+/// for it. Submission is retried `--retries` times on RPC errors. Under
+/// `--dry-run` the call is only printed and `None` is returned. Under
+/// `proxy call` it is first wrapped in `proxy.proxy` for the real account.
+async fn submit_and_wait<Call: subxt::tx::Payload>(
+    opts: &GlobalOpts,
+    api: &OnlineClient<PolkadotConfig>,
+    tx: &Call,
+    signer: &PairSigner<PolkadotConfig, sr25519::Pair>,
+) -> Result<Option<subxt::blocks::ExtrinsicEvents<PolkadotConfig>>, CliError> {
+    match &opts.proxy {
+        Some(proxy) => {
+            // Under `proxy call` the call runs with the real account as its origin
+            let call = RuntimeCall::decode(&mut &api.tx().call_data(tx)?[..])?;
+            let proxied = custom_runtime::tx().proxy().proxy(MultiAddress::Id(proxy.real.clone()), None, call);
+            submit_payload(opts, api, &proxied, signer).await
+        }
+        None => submit_payload(opts, api, tx, signer).await,
+    }
+}
 
-    Ok(())
+/// What `--dry-run` prints instead of submitting a transaction
+#[derive(Serialize)]
+struct DryRunOutput {
+    signer: String,
+    /// Decoded as `Pallet.call(args)`
+    call: String,
+    call_data: String,
+    call_hash: String,
+    tip: Option<u128>,
+    nonce: Option<u64>,
+    /// Blocks the transaction would stay valid for
+    mortality: Option<u64>,
+    estimated_fee: u128,
 }
 
-/// Display registration requirements for a Compute Miner
-async fn handle_register_compute_miner_info() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🖥️ Compute Miner Node Registration Requirements:");
-    println!("------------------------------------------------");
-    println!("1. Node Type: ComputeMiner");
-    println!("2. Required Information:");
-    println!("   a. Node ID: A unique identifier for your compute node");
-    println!("      - Recommended format: Cryptographically secure hash or UUID");
-    println!("      - Example: 'compute-node-01' or a SHA256 hash");
-    println!("   b. IPFS Node ID (Optional):");
-    println!("      - If you're running an IPFS node alongside your compute node");
-    println!("      - Can be retrieved using `ipfs id` command");
-    println!("\n🔧 Technical Recommendations:");
-    println!("- Ensure your node meets minimum compute requirements");
-    println!("- Have a stable internet connection");
-    println!("- Recommended Hardware:");
-    println!("  * CPU: 4+ cores");
-    println!("  * RAM: 16+ GB");
-    println!("  * Storage: 256+ GB SSD");
-    println!("  * Network: 100+ Mbps bandwidth");
-    
-    println!("\n📝 Example Registration Command:");
-    println!("`hippius-cli register-node --type ComputeMiner --node-id <your-unique-node-id>`");
-    
-    Ok(())
+/// What `--show-call-hash` prints before submitting
+#[derive(Serialize)]
+struct CallHashOutput {
+    call_data: String,
+    call_hash: String,
 }
 
-/// Display registration requirements for a Storage Miner
-async fn handle_register_storage_miner_info() -> Result<(), Box<dyn std::error::Error>> {
-    println!("💽 Storage Miner Node Registration Requirements:");
-    println!("------------------------------------------------");
-    println!("1. Node Type: StorageMiner");
-    println!("2. Required Information:");
-    println!("   a. Node ID: A unique identifier for your storage node");
-    println!("      - Recommended format: Cryptographically secure hash or UUID");
-    println!("      - Example: 'storage-node-01' or a SHA256 hash");
-    println!("   b. IPFS Node ID (Recommended):");
-    println!("      - Retrieve using `ipfs id` command");
-    println!("      - Helps in distributed storage network integration");
-    
-    println!("\n🔧 Technical Recommendations:");
-    println!("- High-capacity, reliable storage infrastructure");
-    println!("- Recommended Hardware:");
-    println!("  * Storage: 10+ TB HDD/SSD");
-    println!("  * CPU: 4+ cores");
-    println!("  * RAM: 16+ GB");
-    println!("  * Network: 100+ Mbps bandwidth, stable connection");
-    
-    println!("\n📝 Example Registration Command:");
-    println!("`hippius-cli register-node --type StorageMiner --node-id <your-unique-node-id> --ipfs-node-id <optional-ipfs-node-id>`");
-    
-    Ok(())
+fn print_dry_run(dry_run: &DryRunOutput) {
+    println!("🧪 Dry run, not submitting. Signer: {}", dry_run.signer);
+    if let Some(tip) = dry_run.tip {
+        println!("💸 Tip: {}", tip);
+    }
+    if let Some(nonce) = dry_run.nonce {
+        println!("🔢 Nonce: {}", nonce);
+    }
+    if let Some(blocks) = dry_run.mortality {
+        println!("⏳ Valid for about {} blocks", blocks);
+    }
+    println!("📜 Call: {}", dry_run.call);
+    println!("🧾 Call data: {}", dry_run.call_data);
+    println!("#️⃣  Call hash: {}", dry_run.call_hash);
+    println!("💸 Estimated fee: {}", dry_run.estimated_fee);
 }
 
-/// Display registration requirements for a Validator
-async fn handle_register_validator_info() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🛡️ Validator Node Registration Requirements:");
-    println!("------------------------------------------------");
-    println!("1. Node Type: Validator");
-    println!("2. Required Information:");
-    println!("   a. Node ID: A unique identifier for your validator node");
-    println!("      - Recommended format: Cryptographically secure hash or UUID");
-    println!("      - Example: 'validator-node-01' or a SHA256 hash");
-    
-    println!("\n🔧 Technical Recommendations:");
-    println!("- High uptime and reliability");
-    println!("- Secure and well-maintained infrastructure");
-    println!("- Recommended Hardware:");
-    println!("  * CPU: 8+ cores, high single-thread performance");
-    println!("  * RAM: 32+ GB");
-    println!("  * Storage: 1+ TB SSD (NVMe preferred)");
-    println!("  * Network: 1+ Gbps bandwidth, low latency");
-    
-    println!("\n🔐 Additional Requirements:");
-    println!("- Sufficient stake to be elected as a validator");
-    println!("- Running a full node with latest chain state");
-    println!("- Secure key management");
-    
-    println!("\n📝 Example Registration Command:");
-    println!("`hippius-cli register-node --type Validator --node-id <your-unique-node-id>`");
-    
+/// `submit_and_wait` for a call that needs no further wrapping.
+async fn submit_payload<Call: subxt::tx::Payload>(
+    opts: &GlobalOpts,
+    api: &OnlineClient<PolkadotConfig>,
+    tx: &Call,
+    signer: &PairSigner<PolkadotConfig, sr25519::Pair>,
+) -> Result<Option<subxt::blocks::ExtrinsicEvents<PolkadotConfig>>, CliError> {
+    let call_data = api.tx().call_data(tx)?;
+    if opts.dry_run {
+        let dry_run = DryRunOutput {
+            signer: signer.account_id().to_string(),
+            call: describe_call(&api.metadata(), &call_data)?,
+            call_data: format!("0x{}", hex::encode(&call_data)),
+            call_hash: format!("0x{}", hex::encode(sp_core::hashing::blake2_256(&call_data))),
+            tip: opts.tip,
+            nonce: opts.nonce,
+            mortality: opts.mortality,
+            estimated_fee: estimate_fee(opts, api, tx, signer).await?,
+        };
+        if opts.is_json() {
+            print_json(opts, &dry_run)?;
+        } else {
+            print_dry_run(&dry_run);
+        }
+        return Ok(None);
+    }
+    if opts.estimate_fee {
+        print_fee_estimate(opts, api, tx, signer).await?;
+    }
+    if opts.show_call_hash {
+        let call = CallHashOutput {
+            call_data: format!("0x{}", hex::encode(&call_data)),
+            call_hash: format!("0x{}", hex::encode(sp_core::hashing::blake2_256(&call_data))),
+        };
+        if opts.is_json() {
+            print_json(opts, &call)?;
+        } else {
+            println!("🧾 Call data: {}", call.call_data);
+            println!("#️⃣  Call hash: {}", call.call_hash);
+        }
+    }
+    let mut attempt = 0;
+    let progress = loop {
+        match api.tx().sign_and_submit_then_watch(tx, signer, tx_params(opts, api).await?.build()).await {
+            Ok(progress) => break progress,
+            // Only errors before the node accepted the extrinsic are retried, so nothing is submitted twice
+            Err(subxt::Error::Rpc(e)) if attempt < opts.retries => {
+                attempt += 1;
+                opts.status(format!("🔁 Submission failed ({}), retrying ({}/{})...", e, attempt, opts.retries));
+                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+
+    let tx_hash = progress.extrinsic_hash();
+    log::debug!("📨 Submitted extrinsic {:?}", tx_hash);
+    let stage = match opts.wait_for {
+        WaitFor::InBlock => "included in a block",
+        WaitFor::Finalized => "finalized",
+    };
+    opts.status(format!("⏳ Waiting for transaction to be {}...", stage));
+    let spinner = finalization_spinner(opts);
+    let included = tokio::time::timeout(std::time::Duration::from_secs(opts.finalize_timeout), wait_for_inclusion(progress, opts.wait_for, &spinner)).await;
+    spinner.finish_and_clear();
+    let in_block = included
+        .map_err(|_| {
+            CliError::TxFinalization(format!(
+                "transaction {:?} was not {} within {}s; check a block explorer before resubmitting",
+                tx_hash, stage, opts.finalize_timeout
+            ))
+        })?
+        .map_err(|e| CliError::TxFinalization(format!("transaction {:?} was not {}: {}", tx_hash, stage, e)))?;
+    let events = in_block
+        .wait_for_success()
+        .await
+        .map_err(|e| CliError::TxFailed {
+            message: format!("transaction {:?} failed: {}", tx_hash, e),
+            pallet: match &e {
+                subxt::Error::Runtime(subxt::error::DispatchError::Module(module)) => module.details().ok().map(|details| details.pallet.name().to_string()),
+                _ => None,
+            },
+        })?;
+    let block = api.blocks().at(in_block.block_hash()).await?;
+    opts.status(format!("🔗 Extrinsic {:?} {} in block #{} ({:?})", tx_hash, stage, block.number(), block.hash()));
+
+    if let Some(receipt_dir) = &opts.receipt_dir {
+        let mut event_names = Vec::new();
+        for ev in events.iter() {
+            let ev = ev?;
+            event_names.push(format!("{}.{}", ev.pallet_name(), ev.variant_name()));
+        }
+        let receipt = TxReceipt {
+            command: env::args().skip(1).collect::<Vec<_>>().join(" "),
+            call_hex: format!("0x{}", hex::encode(&call_data)),
+            tx_hash: format!("{:?}", in_block.extrinsic_hash()),
+            block_hash: format!("{:?}", in_block.block_hash()),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs(),
+            signer: signer.account_id().to_string(),
+            events: event_names,
+        };
+        // The transaction is already final, so a failed write must not look like a failed submission
+        if let Err(e) = write_receipt(receipt_dir, &receipt) {
+            log::warn!("⚠️ Transaction succeeded but the receipt could not be written: {}", e);
+        }
+    }
+
+    Ok(Some(events))
+}
+
+/// A spinner on stderr showing how far a submitted transaction has got. Hidden
+/// under `--quiet` and when stdout is not a terminal, so scripts see no noise.
+fn finalization_spinner(opts: &GlobalOpts) -> indicatif::ProgressBar {
+    if opts.quiet || !std::io::stdout().is_terminal() {
+        return indicatif::ProgressBar::hidden();
+    }
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.enable_steady_tick(std::time::Duration::from_millis(120));
+    spinner.set_message("Submitted, waiting for validation...");
+    spinner
+}
+
+/// Follows `progress` until the transaction reaches `wait_for`, like
+/// `TxProgress::wait_for_finalized`, updating `spinner` at every status.
+async fn wait_for_inclusion(
+    mut progress: subxt::tx::TxProgress<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    wait_for: WaitFor,
+    spinner: &indicatif::ProgressBar,
+) -> Result<subxt::tx::TxInBlock<PolkadotConfig, OnlineClient<PolkadotConfig>>, subxt::Error> {
+    use subxt::error::TransactionError;
+    use subxt::tx::TxStatus;
+
+    while let Some(status) = progress.next().await {
+        match status? {
+            TxStatus::Validated => spinner.set_message("Validated, waiting to be broadcast..."),
+            TxStatus::Broadcasted { num_peers } => spinner.set_message(format!("Broadcast to {} peers, waiting for a block...", num_peers)),
+            TxStatus::InBestBlock(in_block) if wait_for == WaitFor::InBlock => return Ok(in_block),
+            TxStatus::InBestBlock(in_block) => spinner.set_message(format!("In block {:?}, waiting for finalization...", in_block.block_hash())),
+            TxStatus::NoLongerInBestBlock => spinner.set_message("Block was retracted, waiting for another..."),
+            TxStatus::InFinalizedBlock(in_block) => return Ok(in_block),
+            TxStatus::Error { message } => return Err(TransactionError::Error(message).into()),
+            TxStatus::Invalid { message } => return Err(TransactionError::Invalid(message).into()),
+            TxStatus::Dropped { message } => return Err(TransactionError::Dropped(message).into()),
+        }
+    }
+    Err(subxt::error::RpcError::SubscriptionDropped.into())
+}
+
+/// Signed extension parameters for every submitted transaction, from `--tip`,
+/// `--nonce` and `--mortality`. Without them the nonce is fetched and the
+/// transaction is immortal, which `--immortal` only makes explicit.
+async fn tx_params(opts: &GlobalOpts, api: &OnlineClient<PolkadotConfig>) -> Result<subxt::config::DefaultExtrinsicParamsBuilder<PolkadotConfig>, CliError> {
+    let mut params = subxt::config::DefaultExtrinsicParamsBuilder::new();
+    if let Some(tip) = opts.tip {
+        params = params.tip(tip);
+    }
+    if let Some(nonce) = opts.nonce {
+        params = params.nonce(nonce);
+    }
+    if let Some(blocks) = opts.mortality {
+        // The era is anchored at the latest block, so it runs out `blocks` blocks from now
+        let latest = api.blocks().at_latest().await?;
+        params = params.mortal(latest.header(), blocks);
+    }
+    Ok(params)
+}
+
+/// Signs `tx` without submitting it and prints the partial fee reported by
+/// the runtime's `TransactionPaymentApi_query_info`.
+async fn print_fee_estimate<Call: subxt::tx::Payload>(
+    opts: &GlobalOpts,
+    api: &OnlineClient<PolkadotConfig>,
+    tx: &Call,
+    signer: &PairSigner<PolkadotConfig, sr25519::Pair>,
+) -> Result<(), CliError> {
+    let fee = estimate_fee(opts, api, tx, signer).await?;
+    opts.status(format!("💸 Estimated fee: {}", fee));
     Ok(())
 }
 
-async fn handle_get_rankings(node_type: CliNodeType, node_id: String) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🏆 Fetching Rankings for Miner: {} ({:?})", node_id, node_type);
+/// The partial fee the runtime expects to charge for `tx`, in the smallest unit.
+async fn estimate_fee<Call: subxt::tx::Payload>(
+    opts: &GlobalOpts,
+    api: &OnlineClient<PolkadotConfig>,
+    tx: &Call,
+    signer: &PairSigner<PolkadotConfig, sr25519::Pair>,
+) -> Result<u128, CliError> {
+    let signed = api.tx().create_signed(tx, signer, tx_params(opts, api).await?.build()).await?;
+    Ok(signed.partial_fee_estimate().await?)
+}
 
-    let (api, _) = setup_substrate_client().await?;
+/// Decodes SCALE call data against the runtime metadata into `Pallet.call(args)`.
+fn describe_call(metadata: &subxt::Metadata, call_data: &[u8]) -> Result<String, CliError> {
+    use subxt::ext::scale_decode::Field;
 
-    // Determine the appropriate storage query based on node type
-    let storage_query = match node_type {
-        CliNodeType::Validator => {
-            println!("Querying Validator Rankings...");
-            subxt::dynamic::storage("RankingValidators", "RankedList", vec![])
-        },
-        CliNodeType::StorageMiner => {
-            println!("Querying Storage Miner Rankings...");
-            subxt::dynamic::storage("RankingStorage", "RankedList", vec![])
-        },
-        CliNodeType::ComputeMiner => {
-            println!("Querying Compute Miner Rankings...");
-            subxt::dynamic::storage("RankingCompute", "RankedList", vec![])
-        },
+    let [pallet_index, call_index, args @ ..] = call_data else {
+        return Err("Call data is too short".into());
     };
+    let pallet = metadata
+        .pallet_by_index(*pallet_index)
+        .ok_or_else(|| format!("Unknown pallet index {}", pallet_index))?;
+    let variant = pallet
+        .call_variant_by_index(*call_index)
+        .ok_or_else(|| format!("Unknown call index {} in {}", call_index, pallet.name()))?;
+
+    let mut fields = variant
+        .fields
+        .iter()
+        .map(|field| Field::new(field.ty.id, field.name.as_deref()));
+    let mut input = args;
+    let args = subxt::ext::scale_value::scale::decode_as_fields(&mut input, &mut fields, metadata.types())?;
+    if !input.is_empty() {
+        return Err(CliError::DecodeFailed(format!("{} trailing bytes after {}.{}", input.len(), pallet.name(), variant.name)));
+    }
 
-    // Fetch the ranked list
-    let ranked_list_result = api.storage().at_latest().await?.fetch(&storage_query).await;
+    // The wrapper has no type of its own; u32::MAX keeps it from matching one
+    let args = subxt::ext::scale_value::Value { value: subxt::ext::scale_value::ValueDef::Composite(args), context: u32::MAX };
+    Ok(format!("{}.{}{}", pallet.name(), variant.name, format_value(&args, metadata.types())))
+}
 
-    match ranked_list_result {
-        Ok(Some(list)) => {
-            // Attempt to decode the list of node rankings
-            let node_rankings: Vec<NodeRankings<u32>> = list.as_type()?;
-            
-            println!("\n📊 Rankings for {:?} Node:", node_type);
-            println!("------------------------");
-
-            // Convert the input node_id to Vec<u8> for comparison
-            let target_node_id = node_id.as_bytes().to_vec();
-
-            // Calculate total weight for normalization
-            let total_weight: u128 = node_rankings.iter().map(|r| r.weight as u128).sum();
-
-            // Iterate through the rankings and find the matching node
-            let mut found = false;
-            for (index, ranking) in node_rankings.iter().enumerate() {
-                if ranking.node_id == target_node_id {
-                    println!("Rank #{}: ", index + 1);
-                    println!("  Node ID: {}", String::from_utf8_lossy(&ranking.node_id));
-                    println!("  Node SS58 Address: {}", String::from_utf8_lossy(&ranking.node_ss58_address));
-                    println!("  Node Type: {:?}", ranking.node_type);
-                    println!("  Weight: {}", ranking.weight);
-                    println!("  Node Ranking: {}", ranking.rank);
-                    println!("  Last Updated: {}", ranking.last_updated);
-                    println!("  Active: {}", ranking.is_active);
-
-                    // Reward estimation logic
-                    match node_type {
-                        CliNodeType::Validator => {
-                            println!("  Estimated Reward: 0 (Validators do not receive direct rewards)");
-                        },
-                        CliNodeType::ComputeMiner => {
-                            // Fetch balance of the pallet
-                            match query_pallet_balance(&api, 2).await {
-                                Ok(balance) => {
-                                    println!("💰 Ranking Pallet Balance: {} tokens", balance);
-                                    let estimated_reward = if total_weight > 0 {
-                                        (ranking.weight as u128 * balance) / total_weight
-                                    } else {
-                                        0
-                                    };
-                                    
-                                    println!("  Estimated Reward: {} tokens", estimated_reward);
-                                },
-                                Err(_e) => {
-                                    println!(" Estimated Reward: 0 ");
-                                },
-                            };
-                            
-
-                        },
-                        CliNodeType::StorageMiner => {
-                            // Fetch balance of the pallet
-                            match query_pallet_balance(&api, 1).await {
-                                Ok(balance) => {
-                                    println!("💰 Ranking Pallet Balance: {} tokens", balance);
-                                    let estimated_reward = if total_weight > 0 {
-                                        (ranking.weight as u128 * balance) / total_weight
-                                    } else {
-                                        0
-                                    };
-                                    
-                                    println!("  Estimated Reward: {} tokens", estimated_reward);
-                                },
-                                Err(_e) => {
-                                    println!(" Estimated Reward: 0 ");
-                                },
-                            };
-                        }
-                    }
+/// Like `Value`'s `Display`, but prints values whose type is an `AccountId32`
+/// as SS58 addresses and other byte arrays (hashes, raw keys) as hex.
+fn format_value(value: &subxt::ext::scale_value::Value<u32>, types: &subxt::ext::scale_value::scale::PortableRegistry) -> String {
+    use subxt::ext::scale_value::stringify::{custom_formatters::format_hex, to_writer_custom};
+
+    // Formatters must be 'static, so look the account types up front
+    let account_types: Vec<u32> = types
+        .types
+        .iter()
+        .filter(|ty| ty.ty.path.segments.last().is_some_and(|name| name == "AccountId32"))
+        .map(|ty| ty.id)
+        .collect();
 
-                    println!("------------------------");
-                    found = true;
-                    break; // Exit the loop once the matching node is found
-                }
+    let mut out = String::new();
+    to_writer_custom()
+        .add_custom_formatter(move |value: &subxt::ext::scale_value::Value<u32>, writer: &mut &mut String| {
+            if !account_types.contains(&value.context) {
+                return None;
             }
+            let mut bytes = Vec::new();
+            let account: [u8; 32] = value_bytes(value, &mut bytes).then_some(bytes)?.try_into().ok()?;
+            Some(std::fmt::Write::write_str(writer, &AccountId32::from(account).to_string()))
+        })
+        .add_custom_formatter(|value, writer: &mut &mut String| format_hex(value, writer))
+        .write(value, &mut out)
+        .expect("writing to a String cannot fail");
+    out
+}
 
-            if !found {
-                println!("❌ No rankings found for the specified node ID: {}", node_id);
+/// Appends the bytes of a value made only of (possibly nested) u8s; false otherwise.
+fn value_bytes<T>(value: &subxt::ext::scale_value::Value<T>, bytes: &mut Vec<u8>) -> bool {
+    use subxt::ext::scale_value::{Primitive, ValueDef};
+
+    match &value.value {
+        ValueDef::Primitive(Primitive::U128(byte)) => match u8::try_from(*byte) {
+            Ok(byte) => {
+                bytes.push(byte);
+                true
             }
+            Err(_) => false,
         },
-        Ok(None) => {
-            println!("No rankings found for {:?} nodes.", node_type);
-        },
-        Err(e) => {
-            eprintln!("🚨 Error querying rankings: {}", e);
-            return Err(e.into());
+        ValueDef::Composite(fields) => fields.values().all(|field| value_bytes(field, bytes)),
+        _ => false,
+    }
+}
+
+#[derive(Serialize)]
+struct SignedMessageOutput {
+    ss58_address: String,
+    public_key: String,
+    signature: String,
+}
+
+#[derive(Serialize)]
+struct VerifiedMessageOutput {
+    ss58_address: String,
+    /// Whether the signature covers the message wrapped in `<Bytes>..</Bytes>`,
+    /// as the polkadot.js extension signs raw payloads
+    wrapped: bool,
+}
+
+/// The message argument, or stdin when it is absent or `-`. Stdin is used
+/// byte for byte, trailing newline included.
+fn read_message(message: Option<&str>) -> Result<Vec<u8>, CliError> {
+    match message {
+        Some(message) if message != "-" => Ok(message.as_bytes().to_vec()),
+        _ => {
+            let mut bytes = Vec::new();
+            std::io::Read::read_to_end(&mut std::io::stdin(), &mut bytes)?;
+            Ok(bytes)
         }
     }
+}
+
+/// Signs the message bytes as-is with the sr25519 "substrate" signing context,
+/// the same scheme `sr25519_verify` and `@polkadot/util-crypto` check.
+fn handle_sign_message(opts: &GlobalOpts, message: Option<&str>, hotkey: Option<&str>) -> Result<(), CliError> {
+    let pair = match hotkey {
+        Some(hotkey) => find_hotkey_pair(hotkey)?
+            .ok_or_else(|| CliError::Invalid(format!("Hotkey '{}' not found in {}", hotkey, get_hotkeys_dir())))?,
+        None => signer_pair(opts, &opts.signer_spec())?,
+    };
+    let message = read_message(message)?;
+    let signature = pair.sign(&message);
 
+    let output = SignedMessageOutput {
+        ss58_address: pair.public().to_ss58check(),
+        public_key: format!("0x{}", hex::encode(pair.public().0)),
+        signature: format!("0x{}", hex::encode(signature.0)),
+    };
+    if opts.is_json() {
+        return print_json(opts, &output);
+    }
+    println!("✍️ Signed {} bytes as {}", message.len(), output.ss58_address);
+    println!("  Public key: {}", output.public_key);
+    println!("  Signature: {}", output.signature);
     Ok(())
 }
 
-#[derive(codec::Decode)]
-struct AccountInfo {
-    nonce: u32,
-    consumers: u32,
-    providers: u32,
-    sufficients: u32,
-    data: AccountData,
+/// Checks the signature against the message as-is, then against the
+/// `<Bytes>`-wrapped form browser wallets produce. A bad signature is an error.
+fn handle_verify_message(opts: &GlobalOpts, message: &str, signature: &str, account: &str) -> Result<(), CliError> {
+    let account = resolve_account(opts, account)?;
+    let signature: [u8; 64] = hex::decode(signature.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| CliError::Invalid("signature must be 64 hex-encoded bytes".to_string()))?;
+    let signature = sr25519::Signature::from_raw(signature);
+    let public = sr25519::Public::from_raw(account.0);
+    let message = read_message(Some(message))?;
+
+    let wrapped = if sr25519::Pair::verify(&signature, &message, &public) {
+        false
+    } else if sr25519::Pair::verify(&signature, [b"<Bytes>".as_slice(), &message, b"</Bytes>"].concat(), &public) {
+        true
+    } else {
+        return Err(CliError::Other(format!("signature is not valid for {}", account)));
+    };
+
+    let output = VerifiedMessageOutput { ss58_address: account.to_string(), wrapped };
+    if opts.is_json() {
+        return print_json(opts, &output);
+    }
+    println!("✅ Signature is valid for {}{}", output.ss58_address, if output.wrapped { " (<Bytes>-wrapped message)" } else { "" });
+    Ok(())
 }
 
-#[derive(codec::Decode)]
-struct AccountData {
-    free: u128,
-    reserved: u128,
-    frozen: u128,  // Corrected field name
-    flags: u128,   // Added missing field
+#[derive(Serialize)]
+struct DecodedCallOutput {
+    /// "call", "signed extrinsic" or "unsigned extrinsic"
+    kind: &'static str,
+    signer: Option<String>,
+    signature: Option<String>,
+    extensions: Vec<ExtensionOutput>,
+    call: String,
+}
+
+#[derive(Serialize)]
+struct ExtensionOutput {
+    name: String,
+    value: String,
 }
 
+/// Decodes `hex` against the metadata the CLI was built with, so it works offline.
+fn handle_decode_call(opts: &GlobalOpts, hex: &str) -> Result<(), CliError> {
+    let bytes = hex::decode(hex.trim().trim_start_matches("0x"))
+        .map_err(|e| CliError::Invalid(format!("not valid hex: {}", e)))?;
+    let metadata = subxt::Metadata::decode(&mut &include_bytes!("../metadata.scale")[..])?;
+    let decoded = decode_call_or_extrinsic(&metadata, &bytes)?;
 
-async fn query_pallet_balance(
-    api: &OnlineClient<PolkadotConfig>, 
-    pallet_id: u128
-) -> Result<u128, Box<dyn std::error::Error>> {
-
-    // compute Ranking Pallet Balance
-    if pallet_id == 2 {
-        let account_id: AccountId32 = "5EYCAe5j7t7RXEmC8rYDo9i4Z6tWLWf1SbncYcPTkRreCc58"
-        .parse()
-        .map_err(|e| format!("Invalid SS58 address: {:?}", e))?;
-
-        let target_account = dynamic::Value::from(account_id.encode());
-        let balance_query = dynamic::storage("System", "Account", vec![target_account]);
-    
-        let balance_result = api.storage().at_latest().await?.fetch(&balance_query).await;
-    
-        match balance_result {
-            Ok(Some(balance_value)) => {
-                match AccountInfo::decode(&mut &balance_value.encoded()[..]) {
-                    Ok(account_info) => {
-                        let free_balance = account_info.data.free;
-                        Ok(free_balance)
-                    }
-                    Err(e) => {
-                        eprintln!("🚨 Failed to decode account info: {:?}", e);
-                        Err("Failed to decode account balance".into())
-                    }
-                }
-            }
-            Ok(None) => {
-                println!("❌ No balance found for the account");
-                Ok(0)
-            }
-            Err(e) => {
-                eprintln!("🚨 Error querying pallet balance: {}", e);
-                Err(e.into())
-            }
-        }
+    if opts.is_json() {
+        return print_json(opts, &decoded);
+    }
+    println!("🔍 Decoded {}", decoded.kind);
+    if let Some(signer) = &decoded.signer {
+        println!("  Signer: {}", signer);
+    }
+    if let Some(signature) = &decoded.signature {
+        println!("  Signature: {}", signature);
+    }
+    for extension in &decoded.extensions {
+        println!("  {}: {}", extension.name, extension.value);
     }
-    else{
-        let account_id: AccountId32 = "5EYCAe5j7t7RXEmC8qLjtLHVtXsw8pj56jCBZEZZM7x5ETVJ"
-        .parse()
-        .map_err(|e| format!("Invalid SS58 address: {:?}", e))?;
+    println!("  Call: {}", decoded.call);
+    Ok(())
+}
 
-        let target_account = dynamic::Value::from(account_id.encode());
-        let balance_query = dynamic::storage("System", "Account", vec![target_account]);
-    
-        let balance_result = api.storage().at_latest().await?.fetch(&balance_query).await;
-    
-        match balance_result {
-            Ok(Some(balance_value)) => {
-                match AccountInfo::decode(&mut &balance_value.encoded()[..]) {
-                    Ok(account_info) => {
-                        let free_balance = account_info.data.free;
-                        Ok(free_balance)
-                    }
-                    Err(e) => {
-                        eprintln!("🚨 Failed to decode account info: {:?}", e);
-                        Err("Failed to decode account balance".into())
-                    }
-                }
-            }
-            Ok(None) => {
-                println!("❌ No balance found for the account");
-                Ok(0)
-            }
-            Err(e) => {
-                eprintln!("🚨 Error querying pallet balance: {}", e);
-                Err(e.into())
-            }
+/// Bytes that carry a compact length prefix matching their size and a v4
+/// version byte are tried as an extrinsic first; anything else, or an
+/// extrinsic that fails to decode, is decoded as bare call data.
+fn decode_call_or_extrinsic(metadata: &subxt::Metadata, bytes: &[u8]) -> Result<DecodedCallOutput, CliError> {
+    let mut input = bytes;
+    let looks_like_extrinsic = matches!(
+        (codec::Compact::<u32>::decode(&mut input), input.first()),
+        (Ok(len), Some(version)) if len.0 as usize == input.len() && version & 0x7f == 4
+    );
+    if looks_like_extrinsic {
+        if let Ok(decoded) = decode_extrinsic(metadata, input) {
+            return Ok(decoded);
         }
     }
+    Ok(DecodedCallOutput {
+        kind: "call",
+        signer: None,
+        signature: None,
+        extensions: Vec::new(),
+        call: describe_call(metadata, bytes)?,
+    })
 }
 
-async fn handle_register_node_with_hotkey(
-    hotkey_address: &str,
-    hips_key: &str,  // New parameter for HIPS key
-    node_type: CliNodeType,
-    node_id: String,
-    pay_in_credits: bool,
-    ipfs_node_id: Option<String>
-) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Initializing Node Registration for: {} ", node_id);
-    
-    // Initialize API client
-    let api = setup_substrate_client().await?.0;
-    
-    // Convert CliNodeType to runtime NodeType
-    let runtime_node_type = match node_type {
-        CliNodeType::Validator => NodeType::Validator,
-        CliNodeType::ComputeMiner => NodeType::ComputeMiner,
-        CliNodeType::StorageMiner => NodeType::StorageMiner,
+/// Decodes a v4 extrinsic body (everything after the length prefix).
+fn decode_extrinsic(metadata: &subxt::Metadata, body: &[u8]) -> Result<DecodedCallOutput, CliError> {
+    use subxt::ext::scale_value::{scale::decode_as_type, ValueDef};
+
+    let [version, rest @ ..] = body else {
+        return Err("Extrinsic is empty".into());
+    };
+    let mut input = rest;
+    let mut decoded = DecodedCallOutput {
+        kind: "unsigned extrinsic",
+        signer: None,
+        signature: None,
+        extensions: Vec::new(),
+        call: String::new(),
     };
 
-    let hotkeys_dir = get_hotkeys_dir();
-    
-    // Load the hotkey mnemonic from the keystore
-    let hotkey_path = format!("{}/{}", hotkeys_dir, hotkey_address);
-    if !Path::new(&hotkey_path).exists() {
-        return Err(format!("Hotkey not found at path: {}", hotkey_path).into());
+    if version & 0x80 != 0 {
+        let extrinsic = metadata.extrinsic();
+        decoded.kind = "signed extrinsic";
+        decoded.signer = Some(match MultiAddress::<AccountId32, ()>::decode(&mut input)? {
+            MultiAddress::Id(account) => account.to_string(),
+            other => format!("{:?}", other),
+        });
+        let signature = decode_as_type(&mut input, extrinsic.signature_ty(), metadata.types())?;
+        decoded.signature = Some(match signature.value {
+            ValueDef::Variant(variant) => variant.name,
+            other => other.to_string(),
+        });
+        for extension in extrinsic.signed_extensions() {
+            let value = decode_as_type(&mut input, extension.extra_ty(), metadata.types())?;
+            // Most extensions carry no data in the extrinsic itself
+            if matches!(&value.value, ValueDef::Composite(fields) if fields.is_empty()) {
+                continue;
+            }
+            decoded.extensions.push(ExtensionOutput {
+                name: extension.identifier().to_string(),
+                value: format_value(&value, metadata.types()),
+            });
+        }
     }
-    
-    let mnemonic = fs::read_to_string(&hotkey_path)?;
-    let mnemonic = Mnemonic::parse_in_normalized(Language::English, mnemonic.trim())?;
-    let seed = mnemonic.to_seed("");
-    let seed_array: [u8; 32] = seed[..32].try_into().map_err(|_| "Seed slice has incorrect length")?;
-    let hotkey_pair = sr25519::Pair::from_seed(&seed_array);
-    
-    // Convert HIPS key string to AccountId32
-    let hips_account = AccountId32::from_str(hips_key)
-        .map_err(|_| "Invalid HIPS key format")?;
-    
-    println!("📤 Preparing transaction to register node...");
-    
-    // Create the transaction with HIPS key as first parameter
-    let tx = custom_runtime::tx().registration().register_node_with_hotkey(
-        hips_account,  // HIPS key as AccountId32
-        runtime_node_type,
-        node_id.clone().into_bytes(),
-        pay_in_credits,
-        ipfs_node_id.map(|id| id.into_bytes())
-    );
 
-    // Create a PairSigner from the hotkey pair
-    let signer = PairSigner::new(hotkey_pair);
-    
-    // Sign with the hotkey
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-    
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
-    
-    println!("✅ Successfully registered node!");
-    println!("📦 Node ID: {}", node_id);
+    decoded.call = describe_call(metadata, input)?;
+    Ok(decoded)
+}
 
+/// Writes `receipt` to `<dir>/<tx_hash>.json`, creating the directory if needed.
+fn write_receipt(dir: &str, receipt: &TxReceipt) -> Result<(), CliError> {
+    fs::create_dir_all(dir)?;
+    let path = Path::new(dir).join(format!("{}.json", receipt.tx_hash));
+    fs::write(&path, serde_json::to_string_pretty(receipt)?)?;
     Ok(())
 }
 
-async fn handle_register_node_with_coldkey(node_type: CliNodeType, node_id: String, pay_in_credits: bool, ipfs_node_id: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Initializing Node Registration for: {} ", node_id);
-    
-    let (api, signer) = setup_substrate_client().await?;
-    
-    // Convert CliNodeType to runtime NodeType
-    let runtime_node_type = match node_type {
-        CliNodeType::Validator => NodeType::Validator,
-        CliNodeType::ComputeMiner => NodeType::ComputeMiner,
-        CliNodeType::StorageMiner => NodeType::StorageMiner,
-    };
-    
-    println!("📤 Submitting transaction to register node...");
-    let tx = custom_runtime::tx().registration().register_node_with_coldkey(runtime_node_type, node_id.clone().into_bytes(), pay_in_credits, ipfs_node_id.map(|id| id.into_bytes()));
+/// `~/.config/hippius/config.toml`, holding the named network profiles
+fn config_path() -> std::path::PathBuf {
+    let home_path = home_dir().expect("Could not find home directory");
+    home_path.join(".config/hippius/config.toml")
+}
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-    
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
-    
-    println!("✅ Successfully registered node!");
-    println!("📦 Node ID: {}", node_id);
+/// Reads the config file and stores the active profile (`--profile`, else
+/// `default_profile`) in `opts`. A missing file is fine unless a profile was
+/// asked for by name.
+fn load_profile(opts: &mut GlobalOpts) -> Result<(), CliError> {
+    let path = config_path();
+    let config: Config = match fs::read_to_string(&path) {
+        Ok(contents) => toml::from_str(&contents).map_err(|e| format!("invalid config {}: {}", path.display(), e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Config::default(),
+        Err(e) => return Err(format!("could not read {}: {}", path.display(), e).into()),
+    };
 
+    let Some(name) = opts.profile.clone().or(config.default_profile) else {
+        return Ok(());
+    };
+    let profile = config
+        .profiles
+        .get(&name)
+        .ok_or_else(|| format!("profile '{}' is not defined in {}", name, path.display()))?;
+    opts.profile = Some(name);
+    opts.profile_settings = profile.clone();
     Ok(())
 }
 
-async fn handle_generate_keys() -> Result<(), Box<dyn std::error::Error>> {
-    // Hardcoded keypair directory
-    let keypair_dir = "/home/faiz/hippius/chains/hippius-testnet/keystore";
+#[derive(Serialize)]
+struct ConfigOutput {
+    config_file: String,
+    profile: Option<String>,
+    rpc_urls: Vec<String>,
+    keystore_path: String,
+    hotkeys_dir: String,
+    signer: String,
+}
 
-    // Ensure directory exists
-    fs::create_dir_all(keypair_dir)?;
+/// Prints the settings commands would actually use, without connecting.
+fn handle_config_show(opts: &GlobalOpts) -> Result<(), CliError> {
+    let signer = match signer_pair(opts, &opts.signer_spec()) {
+        Ok(pair) => pair.public().to_ss58check(),
+        Err(_) => "(not configured)".to_string(),
+    };
+    let output = ConfigOutput {
+        config_file: config_path().display().to_string(),
+        profile: opts.profile.clone(),
+        rpc_urls: rpc_endpoints(opts),
+        keystore_path: opts.keystore_path(),
+        hotkeys_dir: get_hotkeys_dir(),
+        // Only the account is shown; the secret itself never leaves the config
+        signer,
+    };
+
+    if opts.is_json() {
+        return print_json(opts, &output);
+    }
+    println!("📄 Config file: {}", output.config_file);
+    println!("🏷️  Profile: {}", output.profile.as_deref().unwrap_or("(none)"));
+    println!("🌐 RPC endpoints: {}", output.rpc_urls.join(", "));
+    println!("🗝️  Keystore path: {}", output.keystore_path);
+    println!("🔥 Hotkeys directory: {}", output.hotkeys_dir);
+    println!("🔑 Signer: {}", output.signer);
+    Ok(())
+}
 
-    // Generate a new Sr25519 keypair
-    let (pair, seed) = sr25519::Pair::generate();
+#[derive(Serialize)]
+struct WhoamiOutput {
+    signer: Option<String>,
+    rpc_url: Option<String>,
+    chain: Option<String>,
+    coldkey: Option<String>,
+}
 
-    // Serialize keypair components
-    let public_key = pair.public();
-    let public_key_ss58 = public_key.to_ss58check(); // Convert public key to SS58 format
+/// One status view of who commands sign as and where they go. Unreachable
+/// endpoints and a missing signer are reported rather than treated as errors.
+async fn handle_whoami(opts: &GlobalOpts) -> Result<(), CliError> {
+    let signer = signer_pair(opts, &opts.signer_spec()).ok().map(|pair| pair.public().to_ss58check());
 
-    // Prepare file paths
-    let public_key_path = Path::new(keypair_dir).join("public_key.ss58");
-    let seed_path = Path::new(keypair_dir).join("seed.bin");
+    let keystore_path = opts.keystore_path();
+    let coldkey = if Path::new(&keystore_path).is_dir() {
+        find_hips_key(&keystore_path)?.as_deref().and_then(hips_key_address)
+    } else {
+        None
+    };
 
-    // Write public key and seed to files
-    fs::write(&public_key_path, &public_key_ss58)?;
-    fs::write(&seed_path, &seed)?; // Save seed as raw binary
+    // The first endpoint that answers `system_chain`, in connection order
+    let mut rpc_url = None;
+    let mut chain = None;
+    for url in rpc_endpoints(opts) {
+        opts.status(format!("🌐 Querying {}", url));
+        match fetch_chain_name(&url, opts.connect_timeout).await {
+            Ok(name) => {
+                rpc_url = Some(url);
+                chain = Some(name);
+                break;
+            }
+            Err(e) => log::warn!("⚠️ {} failed: {}", url, e),
+        }
+    }
 
-    println!("🔑 Keypair Generated Successfully!");
-    println!("📁 Keypair Directory: {}", keypair_dir);
-    println!("📄 Public Key Path: {}", public_key_path.display());
-    println!("📄 Seed Path: {}", seed_path.display());
+    let output = WhoamiOutput { signer, rpc_url, chain, coldkey };
+    if opts.is_json() {
+        return print_json(opts, &output);
+    }
 
+    println!("🔑 Signer: {}", output.signer.as_deref().unwrap_or("(not configured)"));
+    println!("🌐 RPC endpoint: {}", output.rpc_url.as_deref().unwrap_or("(none reachable)"));
+    println!("⛓️  Network: {}", output.chain.as_deref().unwrap_or("(unknown)"));
+    match &output.coldkey {
+        Some(coldkey) => println!("🗝️  HIPS coldkey: {}", coldkey),
+        None => println!("🗝️  HIPS coldkey: (none in {})", keystore_path),
+    }
+    if let (Some(signer), Some(coldkey)) = (&output.signer, &output.coldkey) {
+        if signer != coldkey {
+            log::warn!("⚠️ The signer is not the coldkey in the keystore");
+        }
+    }
     Ok(())
 }
 
-// async fn handle_lock_credits(amount: u128) -> Result<(), Box<dyn std::error::Error>> {
-//     let (api, signer) = setup_substrate_client().await?;
-
-//     println!("📤 Submitting transaction to lock credits...");
-//     let tx = custom_runtime::tx().credits().lock_credits(amount);
-    
-//     let progress = api
-//         .tx()
-//         .sign_and_submit_then_watch_default(&tx, &signer)
-//         .await?;
-    
-//     println!("⏳ Waiting for transaction to be finalized...");
-//     let _ = progress.wait_for_finalized_success().await?;
-    
-//     println!("✅ Successfully locked {} credits!", amount);
-    
-//     Ok(())
-// }
-
-async fn handle_list_locked_credits() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔒 Fetching Locked Credits...");
+type FinalizedBlock = subxt::blocks::Block<PolkadotConfig, OnlineClient<PolkadotConfig>>;
 
-    let (api, signer) = setup_substrate_client().await?;
+#[derive(Serialize)]
+struct EventOutput {
+    block: u32,
+    pallet: String,
+    event: String,
+    fields: String,
+}
 
-    // Get the signer's account ID
-    let signer_account_id = signer.account_id();
+/// Follows finalized blocks and prints their events, optionally replaying from
+/// `from_block` first. Runs until Ctrl-C or until the subscription ends.
+async fn handle_watch_events(opts: &GlobalOpts, pallet: Option<&str>, mine: bool, from_block: Option<u32>) -> Result<(), CliError> {
+    // Resolve the account before connecting so a missing signer fails fast
+    let account = if mine {
+        Some(signer_pair(opts, &opts.signer_spec())?.public().0)
+    } else {
+        None
+    };
 
-    // Build a dynamic storage query for LockedCredits
-    let storage_query = subxt::dynamic::storage("Credits", "LockedCredits", vec![
-        subxt::dynamic::Value::from_bytes(&signer_account_id.encode())
-    ]);
+    let api = connect_any(opts).await?;
+    let metadata = api.metadata();
+    let mut blocks = api.blocks().subscribe_finalized().await?;
+    opts.status("👀 Watching finalized blocks (Ctrl-C to stop)...");
 
-    // Fetch the locked credits
-    let locked_credits_result = api.storage().at_latest().await?.fetch(&storage_query).await;
+    let Some(first) = blocks.next().await else {
+        return Ok(());
+    };
+    let first = first?;
 
-    match locked_credits_result {
-        Ok(Some(credits_value)) => {
-            // Decode the locked credits
-            let locked_credits: Vec<LockedCredit<AccountId32, u32>> = credits_value.as_type()?;
+    if let Some(from_block) = from_block {
+        for block in blocks_below(&api, &first, from_block, u32::MAX).await? {
+            print_block_events(opts, &metadata, &block, pallet, account.as_ref()).await?;
+        }
+    }
+    print_block_events(opts, &metadata, &first, pallet, account.as_ref()).await?;
 
-            if locked_credits.is_empty() {
-                println!("❌ No locked credits found for your account.");
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                opts.status("👋 Stopped watching");
                 return Ok(());
             }
+            block = blocks.next() => match block {
+                Some(block) => print_block_events(opts, &metadata, &block?, pallet, account.as_ref()).await?,
+                None => return Err("the node closed the block subscription".into()),
+            },
+        }
+    }
+}
 
-            println!("🏦 Locked Credits:");
-            println!("------------------------");
-            for (index, credit) in locked_credits.iter().enumerate() {
-                println!("Lock #{}", index + 1);
-                println!("  Amount Locked: {}", credit.amount_locked);
-                println!("  Created At Block: {}", credit.created_at);
-                println!("  Lock ID: {}", credit.id);
-                println!("  Fulfilled: {}", credit.is_fulfilled);
-                if let Some(tx_hash) = &credit.tx_hash {
-                    println!("  Transaction Hash: {}", String::from_utf8_lossy(tx_hash));
-                }
-                println!("------------------------");
-            }
-
-            let total_locked: u128 = locked_credits.iter().map(|c| c.amount_locked).sum();
-            println!("💰 Total Locked Credits: {}", total_locked);
-        },
-        Ok(None) => {
-            println!("❌ No locked credits found for your account.");
-        },
-        Err(e) => {
-            eprintln!("🚨 Error querying locked credits: {}", e);
-            return Err(e.into());
+/// The blocks numbered `from..=to` below `tip` (which is not included), oldest
+/// first. Found by walking parent hashes back from `tip`, so this is meant for
+/// recent history: every block down to `from` costs a header fetch.
+async fn blocks_below(api: &OnlineClient<PolkadotConfig>, tip: &FinalizedBlock, from: u32, to: u32) -> Result<Vec<FinalizedBlock>, CliError> {
+    let mut blocks = Vec::new();
+    let mut number = tip.number();
+    let mut parent = tip.header().parent_hash;
+    while number > from {
+        let block = api.blocks().at(parent).await?;
+        number = block.number();
+        parent = block.header().parent_hash;
+        if number <= to {
+            blocks.push(block);
         }
     }
+    blocks.reverse();
+    Ok(blocks)
+}
+
+/// Prints the events of `block` that pass the pallet and account filters, one
+/// line (or JSON object) per event.
+async fn print_block_events(
+    opts: &GlobalOpts,
+    metadata: &subxt::Metadata,
+    block: &FinalizedBlock,
+    pallet: Option<&str>,
+    account: Option<&[u8; 32]>,
+) -> Result<(), CliError> {
+    for event in block.events().await?.iter() {
+        let event = event?;
+        if pallet.is_some_and(|pallet| !event.pallet_name().eq_ignore_ascii_case(pallet)) {
+            continue;
+        }
+        if account.is_some_and(|account| !event.field_bytes().windows(32).any(|window| window == account)) {
+            continue;
+        }
 
+        let output = EventOutput {
+            block: block.number(),
+            pallet: event.pallet_name().to_string(),
+            event: event.variant_name().to_string(),
+            fields: format_value(
+                &subxt::ext::scale_value::Value { value: subxt::ext::scale_value::ValueDef::Composite(event.field_values()?), context: u32::MAX },
+                metadata.types(),
+            ),
+        };
+        if opts.is_json() {
+            print_json(opts, &output)?;
+        } else {
+            println!("📣 #{} {}.{} {}", output.block, output.pallet, output.event, output.fields);
+        }
+    }
     Ok(())
 }
 
-async fn handle_bulk_upload(csv_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🗄️ Initiating Bulk File Upload from CSV: {}", csv_path);
+/// Reply of `system_syncState`
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncState {
+    current_block: u64,
+    highest_block: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct NodeHealthOutput {
+    rpc_url: String,
+    chain: String,
+    version: String,
+    roles: Vec<String>,
+    light_client: bool,
+    peers: usize,
+    should_have_peers: bool,
+    syncing: bool,
+    best_block: u64,
+    highest_block: Option<u64>,
+    finalized_block: u64,
+}
+
+/// Reports on the first node from `rpc_endpoints` that answers, so operators can
+/// confirm it is synced and has peers before submitting transactions.
+async fn handle_node_health(opts: &GlobalOpts) -> Result<(), CliError> {
+    let mut failures = Vec::new();
+    let mut connected = None;
+    for url in rpc_endpoints(opts) {
+        opts.status(format!("🌐 Connecting to Substrate node at: {}", url));
+        let connect = subxt::backend::rpc::RpcClient::from_url(&url);
+        match tokio::time::timeout(std::time::Duration::from_secs(opts.connect_timeout), connect).await {
+            Ok(Ok(rpc)) => {
+                connected = Some((url, rpc));
+                break;
+            }
+            Ok(Err(e)) => failures.push(format!("{}: {}", url, e)),
+            Err(_) => failures.push(format!("{}: no answer within {}s", url, opts.connect_timeout)),
+        }
+    }
+    let Some((rpc_url, rpc)) = connected else {
+        return Err(CliError::RpcConnect(format!("no RPC endpoint reachable ({})", failures.join("; "))));
+    };
+
+    let methods = subxt::backend::legacy::LegacyRpcMethods::<PolkadotConfig>::new(rpc.clone());
+    let health = methods.system_health().await?;
+    let chain = methods.system_chain().await?;
+    let version = methods.system_version().await?;
+    let roles: Vec<String> = rpc.request("system_nodeRoles", subxt::backend::rpc::rpc_params![]).await?;
+    let sync: SyncState = rpc.request("system_syncState", subxt::backend::rpc::rpc_params![]).await?;
+    let finalized_hash = methods.chain_get_finalized_head().await?;
+    let finalized_block = methods
+        .chain_get_header(Some(finalized_hash))
+        .await?
+        .map(|header| header.number as u64)
+        .unwrap_or_default();
+
+    let output = NodeHealthOutput {
+        rpc_url,
+        chain,
+        version,
+        light_client: roles.iter().any(|role| role == "Light"),
+        roles,
+        peers: health.peers,
+        should_have_peers: health.should_have_peers,
+        syncing: health.is_syncing,
+        best_block: sync.current_block,
+        highest_block: sync.highest_block,
+        finalized_block,
+    };
+    if opts.is_json() {
+        return print_json(opts, &output);
+    }
+
+    println!("🌐 RPC endpoint: {}", output.rpc_url);
+    println!("⛓️  Chain: {} (node {})", output.chain, output.version);
+    println!("🧭 Roles: {}{}", output.roles.join(", "), if output.light_client { " (light client)" } else { "" });
+    println!("👥 Peers: {}", output.peers);
+    match output.highest_block {
+        Some(highest) if output.syncing => println!("🔄 Syncing: #{} of #{}", output.best_block, highest),
+        _ if output.syncing => println!("🔄 Syncing: #{}", output.best_block),
+        _ => println!("✅ Synced"),
+    }
+    println!("📦 Best block: #{}", output.best_block);
+    println!("🔒 Finalized block: #{}", output.finalized_block);
+    if output.peers == 0 && output.should_have_peers {
+        log::warn!("⚠️ The node has no peers; its view of the chain may be stale");
+    }
+    if output.syncing {
+        log::warn!("⚠️ The node is still syncing; wait for it before submitting transactions");
+    }
+    Ok(())
+}
+
+/// The chain spec name (`system_chain`) reported by the node at `url`.
+async fn fetch_chain_name(url: &str, timeout_secs: u64) -> Result<String, CliError> {
+    let query = async {
+        let rpc = subxt::backend::rpc::RpcClient::from_url(url).await?;
+        subxt::backend::legacy::LegacyRpcMethods::<PolkadotConfig>::new(rpc).system_chain().await
+    };
+    match tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), query).await {
+        Ok(result) => Ok(result?),
+        Err(_) => Err(format!("no answer from {} within {}s", url, timeout_secs).into()),
+    }
+}
+
+/// Where the last endpoint that accepted a connection is remembered
+fn last_rpc_url_path() -> std::path::PathBuf {
+    let home_path = home_dir().expect("Could not find home directory");
+    home_path.join("hippius/last_rpc_url")
+}
+
+/// The RPC endpoints to try, in order: `--rpc-url` flags, else SUBSTRATE_NODE_URL,
+/// else the active profile, else the public endpoint. The last endpoint that
+/// worked is moved to the front.
+fn rpc_endpoints(opts: &GlobalOpts) -> Vec<String> {
+    let mut urls = if opts.rpc_urls.is_empty() {
+        let url = env::var("SUBSTRATE_NODE_URL")
+            .ok()
+            .or_else(|| opts.profile_settings.rpc_url.clone())
+            .unwrap_or_else(|| DEFAULT_RPC_URL.to_string());
+        vec![url]
+    } else {
+        opts.rpc_urls.clone()
+    };
+
+    if let Ok(last_good) = fs::read_to_string(last_rpc_url_path()) {
+        if let Some(pos) = urls.iter().position(|url| url == last_good.trim()) {
+            let url = urls.remove(pos);
+            urls.insert(0, url);
+        }
+    }
+
+    urls
+}
+
+/// Connects to the first reachable endpoint from `rpc_endpoints`, remembering it for next time.
+/// Tries every endpoint up to `--connect-attempts` times, doubling the pause
+/// between rounds, so a node that is still starting up gets a chance to answer.
+async fn connect_any(opts: &GlobalOpts) -> Result<OnlineClient<PolkadotConfig>, CliError> {
+    let urls = rpc_endpoints(opts);
+    let attempts = opts.connect_attempts.max(1);
+    let mut failures = Vec::new();
+
+    for attempt in 1..=attempts {
+        if attempt > 1 {
+            let backoff = 1u64 << (attempt - 2).min(6);
+            opts.status(format!("🔁 Retrying in {}s (attempt {}/{})", backoff, attempt, attempts));
+            tokio::time::sleep(std::time::Duration::from_secs(backoff)).await;
+        }
+
+        failures.clear();
+        for url in &urls {
+            opts.status(format!("🌐 Connecting to Substrate node at: {}", url));
+            match connect_with_timeout(opts, url).await {
+                Ok(api) => {
+                    if urls.len() > 1 {
+                        opts.status(format!("✅ Connected via {}", url));
+                        // Only a preference; failing to persist it must not fail the command
+                        let path = last_rpc_url_path();
+                        let _ = path.parent().map(fs::create_dir_all);
+                        let _ = fs::write(path, url);
+                    }
+                    return Ok(api);
+                }
+                Err(e) => {
+                    log::warn!("⚠️ {} failed: {}", url, e);
+                    failures.push(format!("{}: {}", url, e));
+                }
+            }
+        }
+    }
+
+    Err(CliError::RpcConnect(format!(
+        "no RPC endpoint reachable after {} attempt(s) ({})",
+        attempts,
+        failures.join("; ")
+    )))
+}
+
+/// Open an RPC client, failing fast if the handshake does not finish within `timeout_secs`
+async fn connect_with_timeout(opts: &GlobalOpts, url: &str) -> Result<OnlineClient<PolkadotConfig>, CliError> {
+    let timeout_secs = opts.connect_timeout;
+    let connect = connect_with_cached_metadata(opts, url);
+    match tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), connect).await {
+        Ok(result) => result.map_err(|e| CliError::RpcConnect(e.to_string())),
+        Err(_) => Err(CliError::RpcConnect(format!("could not connect to {} within {}s", url, timeout_secs))),
+    }
+}
+
+fn metadata_cache_dir() -> std::path::PathBuf {
+    let home_path = home_dir().expect("Could not find home directory");
+    home_path.join("hippius/metadata")
+}
+
+/// Builds a client from `~/hippius/metadata/<genesis>-<spec version>.scale` when the
+/// node still runs that runtime version, downloading (and caching) the metadata
+/// otherwise or with `--no-cache`. Cache problems never fail the connection.
+async fn connect_with_cached_metadata(opts: &GlobalOpts, url: &str) -> Result<OnlineClient<PolkadotConfig>, CliError> {
+    let rpc = subxt::backend::rpc::RpcClient::from_url(url).await?;
+    let methods = subxt::backend::legacy::LegacyRpcMethods::<PolkadotConfig>::new(rpc.clone());
+    let genesis_hash = methods.genesis_hash().await?;
+    let version = methods.state_get_runtime_version(None).await?;
+    let runtime_version = subxt::client::RuntimeVersion {
+        spec_version: version.spec_version,
+        transaction_version: version.transaction_version,
+    };
+
+    let genesis_hex = hex::encode(genesis_hash.0);
+    let cache_path = metadata_cache_dir().join(format!("{}-{}.scale", genesis_hex, version.spec_version));
+    log::debug!("🧬 Runtime spec version {} (genesis 0x{})", version.spec_version, genesis_hex);
+    if !opts.no_cache {
+        if let Ok(bytes) = fs::read(&cache_path) {
+            if let Ok(metadata) = subxt::Metadata::decode(&mut &bytes[..]) {
+                log::debug!("🗃️  Using cached metadata {}", cache_path.display());
+                return Ok(OnlineClient::from_rpc_client_with(genesis_hash, runtime_version, metadata, rpc)?);
+            }
+        }
+    }
+
+    let bytes = fetch_metadata_bytes(&rpc, &methods).await?;
+    let metadata = subxt::Metadata::decode(&mut &bytes[..])?;
+    log::debug!("🗃️  Downloaded metadata ({} bytes), caching it at {}", bytes.len(), cache_path.display());
+
+    // Replace whatever was cached for older runtimes of this chain
+    if let Ok(entries) = fs::read_dir(metadata_cache_dir()) {
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with(&genesis_hex) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+    let _ = fs::create_dir_all(metadata_cache_dir());
+    let _ = fs::write(&cache_path, &bytes);
+
+    Ok(OnlineClient::from_rpc_client_with(genesis_hash, runtime_version, metadata, rpc)?)
+}
+
+/// The node's metadata as SCALE bytes: V15 through the runtime API when available
+/// (as subxt itself prefers), else whatever `state_getMetadata` returns.
+async fn fetch_metadata_bytes(
+    rpc: &subxt::backend::rpc::RpcClient,
+    methods: &subxt::backend::legacy::LegacyRpcMethods<PolkadotConfig>,
+) -> Result<Vec<u8>, CliError> {
+    // Older runtimes lack the API; fall through to the legacy call
+    if let Ok(response) = methods.state_call("Metadata_metadata_at_version", Some(&15u32.encode()), None).await {
+        if let Ok(Some(bytes)) = Option::<Vec<u8>>::decode(&mut &response[..]) {
+            return Ok(bytes);
+        }
+    }
+    let hex_metadata: String = rpc.request("state_getMetadata", subxt::backend::rpc::rpc_params![]).await?;
+    Ok(hex::decode(hex_metadata.trim_start_matches("0x"))?)
+}
+
+async fn handle_request_boot(opts: &GlobalOpts, plan_id: H256) -> Result<(), CliError> {
+    log::info!("🚀 Initializing Boot Request for plan: {:?}", plan_id);
+    
+    let (api, signer) = setup_substrate_client(opts).await?;
+    
+    log::info!("📤 Submitting transaction to request boot...");
+    let tx = custom_runtime::tx().compute().request_compute_boot(plan_id);
+
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
+    
+    log::info!("✅ Successfully requested boot!");
+    log::info!("🆔 Plan ID: {:?}", plan_id);
+
+    Ok(())
+}
+
+async fn handle_request_reboot(opts: &GlobalOpts, plan_id: H256) -> Result<(), CliError> {
+    log::info!("🚀 Initializing Reboot Request for plan: {:?}", plan_id);
+    
+    let (api, signer) = setup_substrate_client(opts).await?;
+    
+    log::info!("📤 Submitting transaction to request reboot...");
+    let tx = custom_runtime::tx().compute().request_compute_reboot(plan_id);
+
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
+    
+    log::info!("✅ Successfully requested reboot!");
+
+    Ok(())
+}
+
+async fn handle_request_delete(opts: &GlobalOpts, plan_id: H256) -> Result<(), CliError> {
+    log::info!("🚀 Initializing Delete Request for plan: {:?}", plan_id);
+    
+    let (api, signer) = setup_substrate_client(opts).await?;
+
+    if !opts.dry_run && !confirm(opts, &format!("Delete the compute space of plan {:?}?", plan_id))? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
+    
+    log::info!("📤 Submitting transaction to request delete...");
+    let tx = custom_runtime::tx().compute().request_compute_delete(plan_id);
+
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
+    
+    log::info!("✅ Successfully requested delete!");
+
+    Ok(())
+}
+
+async fn handle_request_stop(opts: &GlobalOpts, plan_id: H256) -> Result<(), CliError> {
+    log::info!("🚀 Initializing Stop Request for plan: {:?}", plan_id);
+    
+    let (api, signer) = setup_substrate_client(opts).await?;
+    
+    log::info!("📤 Submitting transaction to request stop...");
+    let tx = custom_runtime::tx().compute().request_compute_stop(plan_id);
+
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
+    
+    log::info!("✅ Successfully requested stop!");
+
+    Ok(())
+}
+
+async fn handle_purchase_compute_plan(opts: &GlobalOpts, purchase: &ComputePurchase) -> Result<(), CliError> {
+    log::info!("🛒 Initiating Plan Purchase");
+    
+    // Resolved before connecting so a malformed address fails fast
+    let pay_for_account = purchase.pay_for.as_deref().map(|account| resolve_account(opts, account)).transpose()?;
+    let beneficiary = pay_for_account.as_ref().map(|account| format!(" for {}", account)).unwrap_or_default();
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+
+    if !opts.dry_run && !confirm(opts, &format!("Purchase plan {:?} with image {}{}?", purchase.plan_id, purchase.image, beneficiary))? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
+
+    let cloud_init_cid = match &purchase.cloud_init_file {
+        Some(path) => {
+            let api_url = purchase
+                .ipfs_api
+                .clone()
+                .or_else(|| env::var("IPFS_API_URL").ok())
+                .unwrap_or_else(|| "https://relay-fr.hippius.network".to_string());
+
+            log::info!("📦 Uploading {} to IPFS via {}...", path, api_url);
+            let cid = ipfs_add_file(&api_url, Path::new(path)).await?;
+            log::info!("✅ Uploaded cloud-init configuration, CID: {}", cid);
+            Some(cid)
+        }
+        None => purchase.cloud_init_cid.clone(),
+    };
+
+    log::info!("📤 Submitting transaction to purchase plan...");
+    let tx = custom_runtime::tx()
+        .marketplace()
+        .purchase_plan(
+            purchase.plan_id, 
+            purchase.location,
+            purchase.image.clone().into_bytes(),
+            cloud_init_cid.map(|cid| cid.into_bytes()),
+            pay_for_account,
+            purchase.miner_id.clone().map(|id| id.into_bytes())
+        );
+
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
+    
+    log::info!("✅ Successfully purchased plan{}!", beneficiary);
+    log::info!("🆔 Plan ID: {:?}", purchase.plan_id);
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct InstanceOutput {
+    request_id: u128,
+    plan_id: String,
+    image: String,
+    /// Location picked at purchase, from the account's plan subscription
+    location: Option<u32>,
+    status: String,
+    /// Node ID of the miner, unset until one has been assigned
+    miner: Option<String>,
+}
+
+async fn handle_list_instances(opts: &GlobalOpts) -> Result<(), CliError> {
+    log::info!("🖥️ Fetching Compute Instances for Current User");
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+    let account_id = signer.account_id();
+    let storage_client = api.storage().at_latest().await?;
+
+    let requests = storage_client
+        .fetch(&custom_runtime::storage().compute().compute_requests(account_id.clone()))
+        .await?
+        .unwrap_or_default();
+
+    // Compute requests don't carry the location, it's kept on the subscription
+    let location = storage_client
+        .fetch(&custom_runtime::storage().marketplace().user_plan_subscriptions(account_id))
+        .await?
+        .and_then(|subscription| subscription.cdn_location_id);
+
+    let instances: Vec<InstanceOutput> = requests
+        .iter()
+        .map(|request| InstanceOutput {
+            request_id: request.request_id,
+            plan_id: format!("{:?}", request.plan_id),
+            image: bytes_to_display(&request.selected_image.name),
+            location,
+            status: format!("{:?}", request.status),
+            miner: request.miner_id.as_deref().map(bytes_to_display),
+        })
+        .collect();
+
+    if opts.is_json() {
+        return print_json(opts, &instances);
+    }
+
+    if instances.is_empty() {
+        println!("⚠️ No compute instances found for the current user.");
+        return Ok(());
+    }
+
+    println!("🔢 Total Compute Instances: {}", instances.len());
+
+    for instance in &instances {
+        println!("\n🖥️ Instance #{}", instance.request_id);
+        println!("  Plan ID: {}", instance.plan_id);
+        println!("  Image: {}", instance.image);
+        println!("  Location: {}", instance.location.map(|id| id.to_string()).unwrap_or_else(|| "Any".to_string()));
+        println!("  Status: {}", instance.status);
+        println!("  Miner: {}", instance.miner.as_deref().unwrap_or("Not assigned"));
+    }
+
+    Ok(())
+}
+
+/// Picks the `replicas` best-ranked active storage miners from `RankingStorage`,
+/// since `storage_request` takes explicit miners rather than a replica count.
+async fn select_replica_miners(api: &OnlineClient<PolkadotConfig>, replicas: u32) -> Result<Vec<Vec<u8>>, CliError> {
+    let mut rankings: Vec<NodeRankings<u32>> = match api.storage().at_latest().await?.fetch(&ranked_list_query(CliNodeType::StorageMiner)).await? {
+        Some(list) => list.as_type()?,
+        None => Vec::new(),
+    };
+    rankings.retain(|ranking| ranking.is_active);
+    if rankings.len() < replicas as usize {
+        return Err(CliError::Invalid(format!(
+            "{} replicas requested but only {} storage miners are ranked and active",
+            replicas,
+            rankings.len()
+        )));
+    }
+
+    rankings.sort_by_key(|ranking| ranking.rank);
+    rankings.truncate(replicas as usize);
+    log::info!("🎯 Pinning on the {} best-ranked active storage miners:", replicas);
+    for ranking in &rankings {
+        log::info!("   #{} {} (weight {})", ranking.rank, bytes_to_display(&ranking.node_id), ranking.weight);
+    }
+    Ok(rankings.into_iter().map(|ranking| ranking.node_id).collect())
+}
+
+/// Miner IDs as the marketplace takes them: the bytes of each ID as typed
+fn miner_id_bytes(miner_ids: Option<&[String]>) -> Option<Vec<Vec<u8>>> {
+    miner_ids.map(|ids| ids.iter().map(|id| id.trim().as_bytes().to_vec()).collect())
+}
+
+async fn handle_storage_command(
+    opts: &GlobalOpts,
+    storage_command: &StorageCommands,
+) -> Result<(), CliError> {
+    log::info!("🗄️ Initiating Storage Operation");
+    
+    let (api, signer) = setup_substrate_client(opts).await?;
+    
+    match storage_command {
+        StorageCommands::Pin { file_hash, file_name, miner_ids, replicas } => {
+            let miner_ids = match replicas {
+                Some(replicas) => Some(select_replica_miners(&api, *replicas).await?),
+                None => miner_id_bytes(miner_ids.as_deref()),
+            };
+            submit_storage_request(opts, &api, &signer, file_hash, file_name, miner_ids).await?;
+        },
+        StorageCommands::PinFile { path, ipfs_api, miner_ids, replicas } => {
+            // Chosen before uploading so a shortage of miners fails early
+            let miner_ids = match replicas {
+                Some(replicas) => Some(select_replica_miners(&api, *replicas).await?),
+                None => miner_id_bytes(miner_ids.as_deref()),
+            };
+            let api_url = ipfs_api
+                .clone()
+                .or_else(|| env::var("IPFS_API_URL").ok())
+                .unwrap_or_else(|| "https://relay-fr.hippius.network".to_string());
+            let file_name = Path::new(path)
+                .file_name()
+                .ok_or_else(|| format!("{} is not a file", path))?
+                .to_string_lossy()
+                .to_string();
+
+            log::info!("📦 Uploading {} to IPFS via {}...", path, api_url);
+            let cid = ipfs_add_file(&api_url, Path::new(path)).await?;
+            log::info!("✅ Uploaded to IPFS, CID: {}", cid);
+
+            submit_storage_request(opts, &api, &signer, &cid, &file_name, miner_ids).await?;
+        },
+        StorageCommands::Unpin { file_hash } => {
+            if !opts.dry_run && !confirm(opts, &format!("Unpin {}?", file_hash))? {
+                log::info!("🚫 Cancelled");
+                return Ok(());
+            }
+
+            log::info!("🔓 Submitting transaction to unpin file...");
+            let tx = custom_runtime::tx()
+                .marketplace()
+                .storage_unpin_request(file_hash.clone().into());
+
+            if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+                return Ok(());
+            }
+            
+            log::info!("✅ Successfully unpinned file!");
+        }
+        StorageCommands::UnpinBulk { csv_path } => {
+            handle_unpin_bulk(opts, &api, &signer, csv_path).await?;
+        }
+        // These need no signer, so they are dispatched before the client is set up
+        StorageCommands::CostEstimate { .. } => unreachable!("cost-estimate is handled by handle_storage_cost_estimate"),
+        StorageCommands::Status { .. } => unreachable!("status is handled by handle_storage_status"),
+        StorageCommands::Wait { .. } => unreachable!("wait is handled by handle_storage_wait"),
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct StorageCostOutput {
+    size_bytes: u64,
+    price_per_gb: u128,
+    cost_per_period: u128,
+    period_blocks: u32,
+    period_hours: f64,
+    periods: u64,
+    total_cost: u128,
+    specific_miner_fee: u128,
+}
+
+/// Bytes in the GB that `PricePerGbs` is quoted for
+const BYTES_PER_GB: u128 = 1 << 30;
+
+/// Cost of storing `size_bytes` for one billing period at `price_per_gb`,
+/// prorated to the byte and rounded up.
+fn storage_cost(size_bytes: u64, price_per_gb: u128) -> u128 {
+    (size_bytes as u128 * price_per_gb).div_ceil(BYTES_PER_GB)
+}
+
+/// Prices a file from the marketplace's `PricePerGbs`, assuming storage is billed
+/// once per era (`BlocksPerEra`), and shows the extra fee for naming miners.
+async fn handle_storage_cost_estimate(
+    opts: &GlobalOpts,
+    cid: Option<&str>,
+    size_bytes: Option<u64>,
+    periods: u64,
+    ipfs_api: Option<String>,
+) -> Result<(), CliError> {
+    let size_bytes = match (size_bytes, cid) {
+        (Some(size), _) => size,
+        (None, Some(cid)) => {
+            validate_cid(cid)?;
+            let api_url = ipfs_api
+                .or_else(|| env::var("IPFS_API_URL").ok())
+                .unwrap_or_else(|| "https://relay-fr.hippius.network".to_string());
+            log::info!("📏 Reading the size of {} from {}", cid, api_url);
+            ipfs_object_size(&api_url, cid).await?
+        }
+        (None, None) => return Err(CliError::Invalid("pass a CID or --size-bytes".to_string())),
+    };
+
+    let api = connect_any(opts).await?;
+    let storage = api.storage().at_latest().await?;
+    let price_per_gb = storage
+        .fetch(&custom_runtime::storage().marketplace().price_per_gbs())
+        .await?
+        .ok_or("the marketplace has no storage price set (PricePerGbs)")?;
+    let specific_miner_fee = storage
+        .fetch(&custom_runtime::storage().marketplace().specific_miner_request_fee())
+        .await?
+        .unwrap_or_default();
+    let period_blocks = api.constants().at(&custom_runtime::constants().marketplace().blocks_per_era())?;
+    let block_millis = api.constants().at(&custom_runtime::constants().marketplace().block_duration_millis())?;
+
+    let cost_per_period = storage_cost(size_bytes, price_per_gb);
+    let output = StorageCostOutput {
+        size_bytes,
+        price_per_gb,
+        cost_per_period,
+        period_blocks,
+        period_hours: period_blocks as f64 * block_millis as f64 / 3_600_000.0,
+        periods,
+        total_cost: cost_per_period.saturating_mul(periods as u128),
+        specific_miner_fee,
+    };
+    if opts.is_json() {
+        return print_json(opts, &output);
+    }
+
+    println!("📦 Size: {} bytes ({:.3} GB)", output.size_bytes, output.size_bytes as f64 / BYTES_PER_GB as f64);
+    println!("🏷️  Price: {} per GB per era", output.price_per_gb);
+    println!("💸 Cost per era ({} blocks, ~{:.1}h): {}", output.period_blocks, output.period_hours, output.cost_per_period);
+    println!("🧮 Total for {} era(s): {}", output.periods, output.total_cost);
+    if output.specific_miner_fee > 0 {
+        println!("ℹ️  Pinning with --miner-ids or --replicas adds a fee of {}", output.specific_miner_fee);
+    }
+    Ok(())
+}
+
+/// The cumulative size of `cid` (the file and all its blocks) from the IPFS
+/// HTTP API's `/api/v0/object/stat`.
+async fn ipfs_object_size(api_url: &str, cid: &str) -> Result<u64, CliError> {
+    #[derive(serde::Deserialize)]
+    struct ObjectStat {
+        #[serde(rename = "CumulativeSize")]
+        cumulative_size: u64,
+    }
+
+    let url = format!("{}/api/v0/object/stat", api_url.trim_end_matches('/'));
+    let response = reqwest::Client::new().post(&url).query(&[("arg", cid)]).send().await?;
+    if !response.status().is_success() {
+        return Err(format!("IPFS object/stat failed ({}): {}", response.status(), response.text().await?).into());
+    }
+    Ok(response.json::<ObjectStat>().await?.cumulative_size)
+}
+
+/// Submits a `storage_request` pinning one file, optionally on specific miners.
+async fn submit_storage_request(
+    opts: &GlobalOpts,
+    api: &OnlineClient<PolkadotConfig>,
+    signer: &PairSigner<PolkadotConfig, sr25519::Pair>,
+    file_hash: &str,
+    file_name: &str,
+    miner_ids: Option<Vec<Vec<u8>>>,
+) -> Result<(), CliError> {
+    // Create FileInput with file hash and VM name
+    let file_input = FileInput {
+        file_hash: file_hash.as_bytes().to_vec(),
+        file_name: file_name.as_bytes().to_vec(),
+    };
+
+    log::info!("📌 Submitting transaction to pin files...");
+    let tx = custom_runtime::tx()
+        .marketplace()
+        .storage_request(vec![file_input], miner_ids);
+
+    if submit_and_wait(opts, api, &tx, signer).await?.is_none() {
+        return Ok(());
+    }
+
+    log::info!("✅ Successfully pinned files!");
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct StorageStatusOutput {
+    cid: String,
+    requested: bool,
+    file_name: Option<String>,
+    approved: bool,
+    total_replicas: u32,
+    fulfilled_replicas: u32,
+    assigned_miners: Vec<String>,
+    pins: Vec<PinOutput>,
+}
+
+#[derive(Serialize)]
+struct PinOutput {
+    miner_node_id: String,
+    pinned: bool,
+    file_size_in_bytes: u32,
+    created_at: u32,
+}
+
+/// Reads the storage request of `owner` for `cid` and the miners that reported storing it.
+async fn fetch_storage_status(
+    api: &OnlineClient<PolkadotConfig>,
+    owner: &AccountId32,
+    cid: &str,
+) -> Result<StorageStatusOutput, CliError> {
+    let storage_client = api.storage().at_latest().await?;
+    let file_hash = subxt::dynamic::Value::from(cid.as_bytes().to_vec());
+
+    let request_query = subxt::dynamic::storage("IpfsPin", "RequestedPin", vec![
+        subxt::dynamic::Value::from(owner.encode()),
+        file_hash.clone(),
+    ]);
+    let request: Option<StorageRequest<AccountId32, u32>> = match storage_client.fetch(&request_query).await? {
+        Some(value) => value.as_type()?,
+        None => None,
+    };
+
+    let stored_query = subxt::dynamic::storage("IpfsPin", "FileStored", vec![file_hash]);
+    let pins: Vec<PinRequest<u32>> = match storage_client.fetch(&stored_query).await? {
+        Some(value) => value.as_type()?,
+        None => Vec::new(),
+    };
+
+    let output = StorageStatusOutput {
+        cid: cid.to_string(),
+        requested: request.is_some(),
+        file_name: request.as_ref().map(|r| bytes_to_display(&r.file_name)),
+        approved: request.as_ref().is_some_and(|r| r.is_approved),
+        total_replicas: request.as_ref().map_or(0, |r| r.total_replicas),
+        fulfilled_replicas: request.as_ref().map_or(0, |r| r.fullfilled_replicas),
+        assigned_miners: request
+            .as_ref()
+            .and_then(|r| r.miner_ids.as_ref())
+            .map(|ids| ids.iter().map(|id| bytes_to_display(id)).collect())
+            .unwrap_or_default(),
+        pins: pins
+            .iter()
+            .map(|pin| PinOutput {
+                miner_node_id: bytes_to_display(&pin.miner_node_id),
+                pinned: pin.is_pinned,
+                file_size_in_bytes: pin.file_size_in_bytes,
+                created_at: pin.created_at,
+            })
+            .collect(),
+    };
+    Ok(output)
+}
+
+async fn handle_storage_status(opts: &GlobalOpts, account: Option<&str>, cid: &str) -> Result<(), CliError> {
+    let owner = account_or_signer(opts, account)?;
+    let api = connect_any(opts).await?;
+    let output = fetch_storage_status(&api, &owner, cid).await?;
+
+    if opts.is_json() {
+        return print_json(opts, &output);
+    }
+
+    println!("\n📊 Storage Status for {}", output.cid);
+    println!("------------------------");
+    if output.requested {
+        println!("  File Name: {}", output.file_name.as_deref().unwrap_or_default());
+        println!("  Approved: {}", output.approved);
+        println!("  Replicas: {}/{}", output.fulfilled_replicas, output.total_replicas);
+        if output.assigned_miners.is_empty() {
+            println!("  Assigned Miners: (chosen by the network)");
+        } else {
+            println!("  Assigned Miners: {}", output.assigned_miners.join(", "));
+        }
+    } else {
+        println!("  ⚠️ No open storage request for this file from {}", owner);
+    }
+    if output.pins.is_empty() {
+        println!("  Stored By: no miner has reported this file yet");
+    } else {
+        println!("  Stored By:");
+        for pin in &output.pins {
+            println!(
+                "    {} ({}, {} bytes, since block {})",
+                pin.miner_node_id,
+                if pin.pinned { "pinned" } else { "pending" },
+                pin.file_size_in_bytes,
+                pin.created_at
+            );
+        }
+    }
+    println!("------------------------");
+
+    Ok(())
+}
+
+/// Polls the storage state of `cid` every `interval` seconds until `replicas`
+/// miners (by default the replicas requested) have pinned it. Timing out is an error.
+async fn handle_storage_wait(
+    opts: &GlobalOpts,
+    account: Option<&str>,
+    cid: &str,
+    timeout: u64,
+    replicas: Option<u32>,
+    interval: u64,
+) -> Result<(), CliError> {
+    let owner = account_or_signer(opts, account)?;
+    let api = connect_any(opts).await?;
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(timeout);
+    // The request may be cleaned up once fulfilled, so remember what it asked for
+    let mut requested_replicas = None;
+    let mut last_progress = None;
+
+    loop {
+        let status = fetch_storage_status(&api, &owner, cid).await?;
+        if status.requested {
+            requested_replicas = Some(status.total_replicas);
+        }
+        let target = replicas.or(requested_replicas).unwrap_or(1).max(1);
+        let pinned = status.pins.iter().filter(|pin| pin.pinned).count() as u32;
+        let stored = pinned.max(status.fulfilled_replicas);
+
+        if last_progress != Some((stored, target)) {
+            opts.status(format!("⏳ {}: {}/{} replicas stored", cid, stored, target));
+            last_progress = Some((stored, target));
+        }
+        if stored >= target {
+            if opts.is_json() {
+                return print_json(opts, &status);
+            }
+            println!("✅ {} is stored by {} miner(s)", cid, stored);
+            return Ok(());
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            return Err(format!("timed out after {}s with {}/{} replicas stored for {}", timeout, stored, target, cid).into());
+        }
+        tokio::time::sleep_until(deadline.min(tokio::time::Instant::now() + std::time::Duration::from_secs(interval))).await;
+    }
+}
+
+#[derive(Serialize, Default)]
+struct ImageOutput {
+    os_name: String,
+    name: String,
+    description: String,
+    url: String,
+}
+
+async fn handle_list_images(opts: &GlobalOpts, contains: Option<&str>) -> Result<(), CliError> {
+    opts.status("🖼️  Fetching Available OS Disk Images...");
+    
+    let api = connect_any(opts).await?;
+    
+    // Build a dynamic storage query for OS disk image URLs
+    let storage_query = subxt::dynamic::storage("Marketplace", "OSDiskImageUrls", vec![]);
+    
+    // Fetch storage entries
+    let mut results = api.storage().at_latest().await?.iter(storage_query).await?;
+    
+    let mut image_list = Vec::new();
+    
+    // Iterate through results
+    while let Some(kv) = results.next().await {
+        let kv = kv?;
+        let os_name = bytes_to_display(&os_name_from_key(&kv.key_bytes)?);
+        let details: ImageDetails = kv.value.as_type()?;
+        let image = ImageOutput {
+            os_name,
+            name: bytes_to_display(&details.name),
+            description: bytes_to_display(&details.description),
+            url: bytes_to_display(&details.url),
+        };
+
+        if image.url.is_empty() || contains.is_some_and(|needle| !image.os_name.contains(needle)) {
+            continue;
+        }
+        image_list.push(image);
+    }
+
+    if opts.is_json() {
+        return print_json(opts, &image_list);
+    }
+    if opts.output == OutputFormat::Csv {
+        return print_csv(&image_list);
+    }
+    
+    if image_list.is_empty() {
+        println!("No OS disk images found in the marketplace.");
+        return Ok(());
+    }
+    
+    println!("Available OS Disk Images:");
+    println!("--------------------");
+    for image in image_list {
+        println!("OS: {:<10} | URL: {}", image.os_name, image.url);
+    }
+    
+    Ok(())
+}
+
+/// Decodes the OS name from a full `Marketplace.OSDiskImageUrls` storage key:
+/// twox128(pallet) ++ twox128(item) ++ blake2_128(name) ++ SCALE(name).
+fn os_name_from_key(key_bytes: &[u8]) -> Result<Vec<u8>, CliError> {
+    // Storage prefix (2 × 16 bytes) and the Blake2_128Concat hash precede the encoded key
+    let hash = key_bytes.get(32..48).ok_or("OS image storage key is too short")?;
+    let encoded = &key_bytes[48..];
+    // A mismatch means the map's hasher or key type changed; refuse to print garbage
+    if hash != sp_core::hashing::blake2_128(encoded) {
+        return Err("OS image storage key does not match the Blake2_128Concat layout".into());
+    }
+    let mut input = encoded;
+    let os_name = Vec::<u8>::decode(&mut input)?;
+    if !input.is_empty() {
+        return Err("unexpected trailing bytes in OS image storage key".into());
+    }
+    Ok(os_name)
+}
+
+/// Query free credits for signer's account
+#[derive(Serialize)]
+struct CreditsOutput {
+    free_credits: u128,
+}
+
+async fn handle_get_credits(opts: &GlobalOpts) -> Result<(), CliError> {
+    opts.status("💰 Querying Free Credits...");
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+
+    // Use signer's account ID directly
+    let target_account = subxt::dynamic::Value::from_bytes(signer.account_id().encode());
+
+    // Build a dynamic storage query for free credits
+    let storage_query = subxt::dynamic::storage("Credits", "FreeCredits", vec![target_account]);
+
+    // Fetch the credits value
+    let credits_result = api.storage().at_latest().await?.fetch(&storage_query).await?;
+
+    match credits_result {
+        Some(credits_value) => {
+            // Convert credits value to u128
+            let credits: u128 = credits_value.as_type().unwrap_or(0);
+
+            if opts.is_json() {
+                return print_json(opts, &CreditsOutput { free_credits: credits });
+            }
+
+            println!("✅ Free Credits:");
+            println!("🔢 Amount: {}", credits);
+        },
+        None => {
+            if opts.is_json() {
+                return print_json(opts, &CreditsOutput { free_credits: 0 });
+            }
+            println!("❌ No credits found for the account.");
+        }
+    }
+
+    Ok(())
+}
+
+/// HTTP JSON-RPC endpoint of the operator's own node: the first `--rpc-url`
+/// (ws/wss mapped to http/https), else `LOCAL_NODE_RPC_URL`. The profile and
+/// SUBSTRATE_NODE_URL usually point at a public endpoint, so only the flag is honoured.
+fn local_node_rpc_url(opts: &GlobalOpts) -> String {
+    match opts.rpc_urls.first() {
+        Some(url) => url.replacen("wss://", "https://", 1).replacen("ws://", "http://", 1),
+        None => LOCAL_NODE_RPC_URL.to_string(),
+    }
+}
+
+/// Inserts a `hips` key into a node's keystore via `author_insertKey`. This is an
+/// unsafe RPC, so it targets the local node unless `--rpc-url` says otherwise.
+async fn handle_insert_key(opts: &GlobalOpts, seed_phrase: String, public_key: String) -> Result<(), CliError> {
+    let rpc_url = local_node_rpc_url(opts);
+    log::info!("🔑 Inserting key to node at {}...", rpc_url);
+
+    // Prepare the JSON-RPC request payload
+    let client = reqwest::Client::new();
+    let payload = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "author_insertKey",
+        "params": [
+            "hips",  // Hardcoded key type
+            seed_phrase,
+            public_key
+        ]
+    });
+
+    let response = client
+        .post(&rpc_url)
+        .header("Content-Type", "application/json")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to insert key. Status: {}", response.status()).into());
+    }
+
+    // JSON-RPC reports failures with a 200 status and an `error` object
+    let body: serde_json::Value = response.json().await?;
+    if let Some(error) = body.get("error") {
+        return Err(format!(
+            "node rejected author_insertKey: {} (code {})",
+            error["message"].as_str().unwrap_or("unknown error"),
+            error["code"]
+        )
+        .into());
+    }
+
+    log::info!("🔑 Key inserted successfully!");
+    Ok(())
+}
+
+
+/// Query and print node information where the signer is the owner
+#[derive(Serialize)]
+struct NodeInfoOutput {
+    node_id: String,
+    node_type: String,
+    ipfs_node_id: Option<String>,
+    status: String,
+    registered_at: u32,
+    owner: String,
+}
+
+impl From<&NodeInfo<u32, AccountId32>> for NodeInfoOutput {
+    fn from(node_info: &NodeInfo<u32, AccountId32>) -> Self {
+        NodeInfoOutput {
+            node_id: bytes_to_display(&node_info.node_id),
+            node_type: format!("{:?}", node_info.node_type),
+            ipfs_node_id: node_info.ipfs_node_id.as_deref().map(bytes_to_display),
+            status: format!("{:?}", node_info.status),
+            registered_at: node_info.registered_at,
+            owner: node_info.owner.to_string(),
+        }
+    }
+}
+
+/// Shows the nodes registered to `account` (the signer by default), or every
+/// registered node with `all`.
+async fn handle_query_node_info(opts: &GlobalOpts, account: Option<&str>, all: bool) -> Result<(), CliError> {
+    let (api, owner) = if all {
+        log::info!("🔍 Querying all registered nodes...");
+        (connect_any(opts).await?, None)
+    } else if let Some(account) = account {
+        let account = resolve_account(opts, account)?;
+        log::info!("🔍 Querying node registration of {}...", account);
+        (connect_any(opts).await?, Some(account))
+    } else {
+        log::info!("🔍 Querying Node Registration for Your Node...");
+        let (api, signer) = setup_substrate_client(opts).await?;
+        (api, Some(signer.account_id().clone()))
+    };
+
+    let nodes = fetch_registered_nodes(&api, owner.as_ref()).await?;
+    if opts.is_json() {
+        return print_json(opts, &nodes.iter().map(NodeInfoOutput::from).collect::<Vec<_>>());
+    }
+    if nodes.is_empty() {
+        match (&owner, account) {
+            (None, _) => println!("ℹ️ No nodes are registered."),
+            (Some(owner), Some(_)) => println!("❌ No node is registered to {}.", owner),
+            (Some(_), None) => println!("❌ Your node is not registered yet."),
+        }
+    }
+    for node_info in &nodes {
+        print_node_info(node_info);
+    }
+    Ok(())
+}
+
+/// Shows the node registered under `node_id`, read directly by key.
+async fn handle_query_node_by_id(opts: &GlobalOpts, node_id: &str) -> Result<(), CliError> {
+    log::info!("🔍 Querying node {}...", node_id);
+    let api = connect_any(opts).await?;
+    let node_info = fetch_node_by_id(&api, node_id).await?;
+
+    if opts.is_json() {
+        return print_json(opts, &node_info.as_ref().map(NodeInfoOutput::from));
+    }
+    match &node_info {
+        Some(node_info) => print_node_info(node_info),
+        None => println!("❌ No node is registered with ID {}.", node_id),
+    }
+    Ok(())
+}
+
+/// The node registered under `node_id`, looked up by key in `NodeRegistration`
+/// (hotkey registrations) and then `ColdkeyNodeRegistration`.
+async fn fetch_node_by_id(api: &OnlineClient<PolkadotConfig>, node_id: &str) -> Result<Option<NodeInfo<u32, AccountId32>>, CliError> {
+    let storage = api.storage().at_latest().await?;
+    for map in ["NodeRegistration", "ColdkeyNodeRegistration"] {
+        let query = subxt::dynamic::storage("Registration", map, vec![subxt::dynamic::Value::from_bytes(node_id.as_bytes())]);
+        if let Some(value) = storage.fetch(&query).await? {
+            if let Some(node_info) = value.as_type::<Option<NodeInfo<u32, AccountId32>>>()? {
+                return Ok(Some(node_info));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Every registered node owned by `owner`, or all of them when `owner` is
+/// `None`, from both `NodeRegistration` (hotkey registrations) and
+/// `ColdkeyNodeRegistration`. The maps are keyed by node ID, not owner, so this
+/// scans them; use `fetch_node_by_id` when the ID is known.
+async fn fetch_registered_nodes(
+    api: &OnlineClient<PolkadotConfig>,
+    owner: Option<&AccountId32>,
+) -> Result<Vec<NodeInfo<u32, AccountId32>>, CliError> {
+    let storage = api.storage().at_latest().await?;
+
+    let mut nodes = Vec::new();
+    let mut seen = std::collections::BTreeSet::new();
+    for map in ["NodeRegistration", "ColdkeyNodeRegistration"] {
+        let mut results = storage.iter(subxt::dynamic::storage("Registration", map, vec![])).await?;
+        while let Some(kv) = results.next().await {
+            let node_info: Option<NodeInfo<u32, AccountId32>> = kv?.value.as_type()?;
+            if let Some(node_info) = node_info {
+                // As in `fetch_node_by_id`, a hotkey registration wins over a coldkey one
+                if seen.insert(node_info.node_id.clone()) && owner.is_none_or(|owner| node_info.owner == *owner) {
+                    nodes.push(node_info);
+                }
+            }
+        }
+    }
+    Ok(nodes)
+}
+
+fn print_node_info(node_info: &NodeInfo<u32, AccountId32>) {
+    print!("{}", format_node_info(&NodeInfoOutput::from(node_info)));
+}
+
+/// The text block `print_node_info` prints for one node
+fn format_node_info(output: &NodeInfoOutput) -> String {
+    format!(
+        "✅ Node Information:\n\
+         ------------------------\n\
+         Node ID: {}\n\
+         Node Type: {}\n\
+         IPFS Node ID: {}\n\
+         Status: {}\n\
+         Registered At: {}\n\
+         Owner: {}\n\
+         ------------------------\n",
+        output.node_id,
+        output.node_type,
+        output.ipfs_node_id.as_deref().unwrap_or("None"),
+        output.status,
+        output.registered_at,
+        output.owner,
+    )
+}
+
+/// Prints the signer's registered nodes, as `compute-infos` and `storage-infos` start with
+async fn print_signer_nodes(opts: &GlobalOpts) -> Result<(), CliError> {
+    log::info!("🔍 Querying Node Registration for Your Node...");
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+    let nodes = fetch_registered_nodes(&api, Some(signer.account_id())).await?;
+    if nodes.is_empty() {
+        println!("❌ Your node is not registered yet.");
+    }
+    for node_info in &nodes {
+        print_node_info(node_info);
+    }
+    Ok(())
+}
+
+/// Fetch and display compute-related information
+async fn handle_compute_infos(opts: &GlobalOpts) -> Result<(), CliError> {
+    print_signer_nodes(opts).await?;
+
+    log::info!("🖥️ Fetching Compute Information...");
+
+    // Fetch libvirt version
+    let libvirt_version = Command::new("libvirtd")
+        .arg("--version")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|_| "Not installed".to_string());
+    println!("📦 Libvirt Version: {}", libvirt_version);
+
+    Ok(())
+}
+
+/// Fetch and display storage-related information
+async fn handle_storage_infos(opts: &GlobalOpts) -> Result<(), CliError> {
+    print_signer_nodes(opts).await?;
+
+    log::info!("💽 Fetching Storage Information...");
+
+    // Fetch IPFS version
+    let ipfs_version = Command::new("ipfs")
+        .arg("--version")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|_| "Not installed".to_string());
+    println!("📦 IPFS Version of your node is : {}", ipfs_version);
+
+
+    Ok(())
+}
+
+/// Display registration requirements for a Compute Miner
+async fn handle_register_compute_miner_info() -> Result<(), CliError> {
+    println!("🖥️ Compute Miner Node Registration Requirements:");
+    println!("------------------------------------------------");
+    println!("1. Node Type: ComputeMiner");
+    println!("2. Required Information:");
+    println!("   a. Node ID: The libp2p peer ID of your compute node");
+    println!("      - Printed by `hipc get-node-id` when run against the node");
+    println!("      - Example: '12D3KooW...' (base58) or its CIDv1 form");
+    println!("   b. IPFS Node ID (Required):");
+    println!("      - The ID of the IPFS node running alongside your compute node");
+    println!("      - Can be retrieved using `ipfs id` command, or filled in with --auto-detect");
+    println!("\n🔧 Technical Recommendations:");
+    println!("- Ensure your node meets minimum compute requirements");
+    println!("- Have a stable internet connection");
+    println!("- Recommended Hardware:");
+    println!("  * CPU: 4+ cores");
+    println!("  * RAM: 16+ GB");
+    println!("  * Storage: 256+ GB SSD");
+    println!("  * Network: 100+ Mbps bandwidth");
+    
+    println!("\n📝 Example Registration Command:");
+    println!("`hipc register-node-with-coldkey --node-type compute-miner --node-id <peer-id> --ipfs-node-id <ipfs-node-id>`");
+    println!("`hipc register-node-with-coldkey --node-type compute-miner --auto-detect`");
+    
+    Ok(())
+}
+
+/// Display registration requirements for a Storage Miner
+async fn handle_register_storage_miner_info() -> Result<(), CliError> {
+    println!("💽 Storage Miner Node Registration Requirements:");
+    println!("------------------------------------------------");
+    println!("1. Node Type: StorageMiner");
+    println!("2. Required Information:");
+    println!("   a. Node ID: The libp2p peer ID of your storage node");
+    println!("      - Printed by `hipc get-node-id` when run against the node");
+    println!("      - Example: '12D3KooW...' (base58) or its CIDv1 form");
+    println!("   b. IPFS Node ID (Required):");
+    println!("      - Retrieve using `ipfs id` command, or fill it in with --auto-detect");
+    println!("      - Helps in distributed storage network integration");
+    
+    println!("\n🔧 Technical Recommendations:");
+    println!("- High-capacity, reliable storage infrastructure");
+    println!("- Recommended Hardware:");
+    println!("  * Storage: 10+ TB HDD/SSD");
+    println!("  * CPU: 4+ cores");
+    println!("  * RAM: 16+ GB");
+    println!("  * Network: 100+ Mbps bandwidth, stable connection");
+    
+    println!("\n📝 Example Registration Command:");
+    println!("`hipc register-node-with-coldkey --node-type storage-miner --node-id <peer-id> --ipfs-node-id <ipfs-node-id>`");
+    println!("`hipc register-node-with-coldkey --node-type storage-miner --auto-detect`");
+    
+    Ok(())
+}
+
+/// Display registration requirements for a Validator
+async fn handle_register_validator_info() -> Result<(), CliError> {
+    println!("🛡️ Validator Node Registration Requirements:");
+    println!("------------------------------------------------");
+    println!("1. Node Type: Validator");
+    println!("2. Required Information:");
+    println!("   a. Node ID: The libp2p peer ID of your validator node");
+    println!("      - Printed by `hipc get-node-id` when run against the node");
+    println!("      - Example: '12D3KooW...' (base58) or its CIDv1 form");
+    
+    println!("\n🔧 Technical Recommendations:");
+    println!("- High uptime and reliability");
+    println!("- Secure and well-maintained infrastructure");
+    println!("- Recommended Hardware:");
+    println!("  * CPU: 8+ cores, high single-thread performance");
+    println!("  * RAM: 32+ GB");
+    println!("  * Storage: 1+ TB SSD (NVMe preferred)");
+    println!("  * Network: 1+ Gbps bandwidth, low latency");
+    
+    println!("\n🔐 Additional Requirements:");
+    println!("- Sufficient stake to be elected as a validator");
+    println!("- Running a full node with latest chain state");
+    println!("- Secure key management");
+    
+    println!("\n📝 Example Registration Command:");
+    println!("`hippius-cli register-node --type Validator --node-id <your-unique-node-id>`");
+    
+    Ok(())
+}
+
+/// Prints the ranking of `node_id`, or with `None` the whole ranked list sorted
+/// by rank. A node ID that is not ranked is an error.
+async fn handle_get_rankings(opts: &GlobalOpts, node_type: CliNodeType, node_id: Option<&str>, since_block: Option<u32>) -> Result<(), CliError> {
+    match node_id {
+        Some(node_id) => log::info!("🏆 Fetching Rankings for Miner: {} ({:?})", node_id, node_type),
+        None => log::info!("🏆 Fetching all {:?} Rankings", node_type),
+    }
+
+    let api = connect_any(opts).await?;
+
+    // Determine the appropriate storage query based on node type
+    let storage_query = ranked_list_query(node_type);
+
+    // Fetch the ranked list
+    let mut node_rankings: Vec<NodeRankings<u32>> = match api.storage().at_latest().await?.fetch(&storage_query).await? {
+        Some(list) => list.as_type()?,
+        None => Vec::new(),
+    };
+
+    // Calculate total weight for normalization
+    let total_weight: u128 = node_rankings.iter().map(|r| r.weight as u128).sum();
+
+    // The pool is shared by every row, so it is fetched once
+    let pool = match node_type {
+        CliNodeType::Validator => None,
+        CliNodeType::ComputeMiner | CliNodeType::StorageMiner => {
+            let pallet = if node_type == CliNodeType::ComputeMiner { PalletId::RankingCompute } else { PalletId::RankingStorage };
+            query_pallet_balance(&api, pallet).await.ok()
+        }
+    };
+
+    if node_id.is_none() && opts.output == OutputFormat::Csv {
+        node_rankings.sort_by_key(|ranking| ranking.rank);
+        let rows: Vec<RankingOutput> = node_rankings
+            .iter()
+            .map(|ranking| RankingOutput::new(ranking, node_type, pool, total_weight))
+            .collect();
+        return print_csv(&rows);
+    }
+
+    println!("\n📊 Rankings for {:?} Node:", node_type);
+    if let Some(balance) = pool {
+        println!("💰 Ranking Pallet Balance: {} tokens", balance);
+    }
+    println!("------------------------");
+
+    match node_id {
+        Some(node_id) => {
+            let (index, ranking) = node_rankings
+                .iter()
+                .enumerate()
+                .find(|(_, ranking)| ranking.node_id == node_id.as_bytes())
+                .ok_or_else(|| format!("no rankings found for the specified node ID: {}", node_id))?;
+            println!("Rank #{}: ", index + 1);
+            print_ranking(ranking, node_type, pool, total_weight);
+            if let Some(since_block) = since_block {
+                print_earned_since(opts, &api, ranking, since_block).await?;
+            }
+        }
+        None => {
+            if node_rankings.is_empty() {
+                println!("No rankings found for {:?} nodes.", node_type);
+                return Ok(());
+            }
+            node_rankings.sort_by_key(|ranking| ranking.rank);
+            for ranking in &node_rankings {
+                println!("Rank #{}: ", ranking.rank);
+                print_ranking(ranking, node_type, pool, total_weight);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// One `get-rankings --output csv` row
+#[derive(Serialize, Default)]
+struct RankingOutput {
+    rank: u32,
+    node_id: String,
+    node_ss58_address: String,
+    node_type: String,
+    weight: u16,
+    last_updated: u32,
+    active: bool,
+    /// Share of the reward pool; 0 for validators or when the pool is unknown
+    estimated_reward: u128,
+}
+
+impl RankingOutput {
+    fn new(ranking: &NodeRankings<u32>, node_type: CliNodeType, pool: Option<u128>, total_weight: u128) -> Self {
+        let estimated_reward = match (node_type, pool) {
+            (CliNodeType::Validator, _) | (_, None) => 0,
+            (_, Some(balance)) => estimate_reward(ranking.weight, balance, total_weight),
+        };
+        RankingOutput {
+            rank: ranking.rank,
+            node_id: bytes_to_display(&ranking.node_id),
+            node_ss58_address: bytes_to_display(&ranking.node_ss58_address),
+            node_type: format!("{:?}", ranking.node_type),
+            weight: ranking.weight,
+            last_updated: ranking.last_updated,
+            active: ranking.is_active,
+            estimated_reward,
+        }
+    }
+}
+
+/// Prints one ranked node with its share of the reward `pool`, if known.
+fn print_ranking(ranking: &NodeRankings<u32>, node_type: CliNodeType, pool: Option<u128>, total_weight: u128) {
+    println!("  Node ID: {}", bytes_to_display(&ranking.node_id));
+    println!("  Node SS58 Address: {}", bytes_to_display(&ranking.node_ss58_address));
+    println!("  Node Type: {:?}", ranking.node_type);
+    println!("  Weight: {}", ranking.weight);
+    println!("  Node Ranking: {}", ranking.rank);
+    println!("  Last Updated: {}", ranking.last_updated);
+    println!("  Active: {}", ranking.is_active);
+
+    // Reward estimation logic
+    match (node_type, pool) {
+        (CliNodeType::Validator, _) => {
+            println!("  Estimated Reward: 0 (Validators do not receive direct rewards)");
+        },
+        (_, Some(balance)) => {
+            println!("  Estimated Reward: {} tokens", estimate_reward(ranking.weight, balance, total_weight));
+        },
+        (_, None) => {
+            println!("  Estimated Reward: 0 ");
+        },
+    }
+
+    println!("------------------------");
+}
+
+/// Prints how the free balance of a ranked node's account changed between
+/// `since_block` and the latest block, the amount it actually earned rather
+/// than an estimate. Fails clearly when the node has pruned that block's state.
+async fn print_earned_since(opts: &GlobalOpts, api: &OnlineClient<PolkadotConfig>, ranking: &NodeRankings<u32>, since_block: u32) -> Result<(), CliError> {
+    let account = ranking_account(ranking)?;
+
+    let latest = api.blocks().at_latest().await?;
+    if since_block > latest.number() {
+        return Err(CliError::Invalid(format!("--since-block {} is after the latest block #{}", since_block, latest.number())));
+    }
+    let hash = block_hash_by_number(opts, since_block)
+        .await?
+        .ok_or_else(|| format!("block #{} is not known to the node", since_block))?;
+    let then = match fetch_account_info_at(&api.storage().at(hash), &account).await {
+        Ok(info) => info.data.free,
+        Err(e) => {
+            return Err(format!(
+                "the state of block #{} is not available ({}); the node has probably pruned it, so use an archive node or a more recent --since-block",
+                since_block, e
+            )
+            .into())
+        }
+    };
+    let now = fetch_account_info_at(&latest.storage(), &account).await?.data.free;
+
+    println!(
+        "  Earned Since Block #{}: {:+} tokens ({} → {} over {} blocks)",
+        since_block,
+        now as i128 - then as i128,
+        then,
+        now,
+        latest.number() - since_block
+    );
+    Ok(())
+}
+
+/// The account a ranked node is paid to, from its stored SS58 address
+fn ranking_account(ranking: &NodeRankings<u32>) -> Result<AccountId32, CliError> {
+    std::str::from_utf8(&ranking.node_ss58_address)
+        .ok()
+        .and_then(|address| AccountId32::from_str(address).ok())
+        .ok_or_else(|| format!("the ranking of {} has no valid SS58 address ({})", bytes_to_display(&ranking.node_id), bytes_to_display(&ranking.node_ss58_address)).into())
+}
+
+/// The hash of block `number` on the first reachable endpoint, via `chain_getBlockHash`.
+async fn block_hash_by_number(opts: &GlobalOpts, number: u32) -> Result<Option<H256>, CliError> {
+    let mut failures = Vec::new();
+    for url in rpc_endpoints(opts) {
+        let query = async {
+            let rpc = subxt::backend::rpc::RpcClient::from_url(&url).await?;
+            subxt::backend::legacy::LegacyRpcMethods::<PolkadotConfig>::new(rpc)
+                .chain_get_block_hash(Some(number.into()))
+                .await
+        };
+        match tokio::time::timeout(std::time::Duration::from_secs(opts.connect_timeout), query).await {
+            Ok(Ok(hash)) => return Ok(hash),
+            Ok(Err(e)) => failures.push(format!("{}: {}", url, e)),
+            Err(_) => failures.push(format!("{}: no answer within {}s", url, opts.connect_timeout)),
+        }
+    }
+    Err(CliError::RpcConnect(failures.join("; ")))
+}
+
+/// A node's share of the reward pool `balance`, proportional to its weight.
+fn estimate_reward(weight: u16, balance: u128, total_weight: u128) -> u128 {
+    (weight as u128 * balance).checked_div(total_weight).unwrap_or(0)
+}
+
+/// Builds the `RankedList` storage query of the ranking pallet for a node type.
+fn ranked_list_query(node_type: CliNodeType) -> subxt::storage::DynamicAddress<Vec<subxt::dynamic::Value>> {
+    let pallet = match node_type {
+        CliNodeType::Validator => {
+            log::info!("Querying Validator Rankings...");
+            "RankingValidators"
+        },
+        CliNodeType::StorageMiner => {
+            log::info!("Querying Storage Miner Rankings...");
+            "RankingStorage"
+        },
+        CliNodeType::ComputeMiner => {
+            log::info!("Querying Compute Miner Rankings...");
+            "RankingCompute"
+        },
+    };
+    subxt::dynamic::storage(pallet, "RankedList", vec![])
+}
+
+/// Print the ranking pallet's distributable balance and the total weight it is shared across
+async fn handle_reward_pool(opts: &GlobalOpts, node_type: CliNodeType) -> Result<(), CliError> {
+    log::info!("🏦 Fetching Reward Pool for {:?} nodes", node_type);
+
+    let api = connect_any(opts).await?;
+
+    let pallet = match node_type {
+        CliNodeType::Validator => {
+            println!("ℹ️ Validators do not receive rewards from a ranking pallet.");
+            return Ok(());
+        },
+        CliNodeType::StorageMiner => PalletId::RankingStorage,
+        CliNodeType::ComputeMiner => PalletId::RankingCompute,
+    };
+
+    let storage_query = ranked_list_query(node_type);
+    let node_rankings: Vec<NodeRankings<u32>> = match api.storage().at_latest().await?.fetch(&storage_query).await? {
+        Some(list) => list.as_type()?,
+        None => Vec::new(),
+    };
+
+    let total_weight: u128 = node_rankings.iter().map(|r| r.weight as u128).sum();
+    let active_nodes = node_rankings.iter().filter(|r| r.is_active).count();
+    let balance = query_pallet_balance(&api, pallet).await?;
+
+    println!("\n📊 Reward Pool for {:?} Nodes:", node_type);
+    println!("------------------------");
+    println!("  Distributable Balance: {} tokens", balance);
+    println!("  Total Weight: {}", total_weight);
+    println!("  Ranked Nodes: {} ({} active)", node_rankings.len(), active_nodes);
+    println!("------------------------");
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct RewardReading {
+    block: u32,
+    rank: u32,
+    weight: u16,
+    active: bool,
+    free_balance: u128,
+    /// Change of the free balance since the previous reading
+    delta: i128,
+    /// Change of the free balance since the first reading
+    earned: i128,
+    estimated_per_era: u128,
+}
+
+/// Polls the ranking pallet and the miner's free balance every `interval` seconds
+/// until Ctrl-C, printing the balance change since the last and the first
+/// reading next to the per-era estimate `get-rankings` shows. Balance changes
+/// include anything else that moved the miner's funds, such as transfers.
+async fn handle_watch_rewards(opts: &GlobalOpts, node_type: CliNodeType, node_id: &str, interval: u64, once: bool) -> Result<(), CliError> {
+    let pallet = match node_type {
+        CliNodeType::Validator => {
+            return Err(CliError::Invalid("validators do not receive rewards from a ranking pallet; use compute-miner or storage-miner".to_string()));
+        }
+        CliNodeType::StorageMiner => PalletId::RankingStorage,
+        CliNodeType::ComputeMiner => PalletId::RankingCompute,
+    };
+    log::info!("👀 Watching rewards of {} ({:?}) every {}s", node_id, node_type, interval);
+
+    let api = connect_any(opts).await?;
+    let mut first_balance = None;
+    let mut last_balance = None;
+    loop {
+        let block = api.blocks().at_latest().await?;
+        let node_rankings: Vec<NodeRankings<u32>> = match block.storage().fetch(&ranked_list_query(node_type)).await? {
+            Some(list) => list.as_type()?,
+            None => Vec::new(),
+        };
+        let ranking = node_rankings
+            .iter()
+            .find(|ranking| ranking.node_id == node_id.as_bytes())
+            .ok_or_else(|| format!("no rankings found for the specified node ID: {}", node_id))?;
+        let total_weight: u128 = node_rankings.iter().map(|r| r.weight as u128).sum();
+        let pool = query_pallet_balance(&api, pallet).await?;
+
+        let account = ranking_account(ranking)?;
+        let free_balance = query_free_balance(&api, &account).await?;
+        let first = *first_balance.get_or_insert(free_balance);
+        let reading = RewardReading {
+            block: block.number(),
+            rank: ranking.rank,
+            weight: ranking.weight,
+            active: ranking.is_active,
+            free_balance,
+            delta: free_balance as i128 - last_balance.unwrap_or(free_balance) as i128,
+            earned: free_balance as i128 - first as i128,
+            estimated_per_era: estimate_reward(ranking.weight, pool, total_weight),
+        };
+        last_balance = Some(free_balance);
+
+        if opts.is_json() {
+            print_json(opts, &reading)?;
+        } else {
+            println!(
+                "🕒 #{} rank {} weight {}{} | balance {} ({:+}, {:+} since start) | est. {} per era",
+                reading.block,
+                reading.rank,
+                reading.weight,
+                if reading.active { "" } else { " (inactive)" },
+                reading.free_balance,
+                reading.delta,
+                reading.earned,
+                reading.estimated_per_era,
+            );
+        }
+
+        if once {
+            return Ok(());
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+    }
+}
+
+#[derive(codec::Decode, Default)]
+#[allow(dead_code)] // mirrors the on-chain layout; not every field is read
+struct AccountInfo {
+    nonce: u32,
+    consumers: u32,
+    providers: u32,
+    sufficients: u32,
+    data: AccountData,
+}
+
+#[derive(codec::Decode, Default)]
+#[allow(dead_code)] // mirrors the on-chain layout; not every field is read
+struct AccountData {
+    free: u128,
+    reserved: u128,
+    frozen: u128,  // Corrected field name
+    flags: u128,   // Added missing field
+}
+
+
+/// Pallets whose sovereign account holds funds the CLI reports on
+#[derive(Copy, Clone, Debug)]
+enum PalletId {
+    /// `RankingStorage`, which pays storage miners
+    RankingStorage,
+    /// `RankingCompute`, which pays compute miners
+    RankingCompute,
+}
+
+impl PalletId {
+    /// The pallet's 8-byte `frame_support::PalletId`, as configured in the runtime
+    fn id(self) -> [u8; 8] {
+        match self {
+            PalletId::RankingStorage => *b"ranking1",
+            PalletId::RankingCompute => *b"ranking2",
+        }
+    }
+
+    /// The pallet's sovereign account: `modl` followed by the pallet id, zero padded.
+    fn account_id(self) -> AccountId32 {
+        let mut account = [0u8; 32];
+        account[..4].copy_from_slice(b"modl");
+        account[4..12].copy_from_slice(&self.id());
+        AccountId32::from(account)
+    }
+}
+
+/// Free balance of a pallet's sovereign account.
+async fn query_pallet_balance(
+    api: &OnlineClient<PolkadotConfig>,
+    pallet: PalletId,
+) -> Result<u128, CliError> {
+    Ok(fetch_account_info(api, &pallet.account_id()).await?.data.free)
+}
+
+async fn handle_register_node_with_hotkey(
+    opts: &GlobalOpts,
+    hotkey_address: &str,
+    hips_key: &str,
+    node_type: CliNodeType,
+    node_id: String,
+    pay_in_credits: bool,
+    ipfs_node_id: Option<String>
+) -> Result<(), CliError> {
+    log::info!("🚀 Initializing Node Registration for: {} ", node_id);
+    require_ipfs_node_id(node_type, ipfs_node_id.as_deref())?;
+    
+    let hips_account = resolve_account(opts, hips_key)?;
+
+    // Load the hotkey from the keystore by name or address
+    let signer = resolve_signer(opts, &SignerSpec::Hotkey(hotkey_address.to_string()))?;
+
+    // Initialize API client
+    let api = connect_any(opts).await?;
+    
+    // Convert CliNodeType to runtime NodeType
+    let runtime_node_type = match node_type {
+        CliNodeType::Validator => NodeType::Validator,
+        CliNodeType::ComputeMiner => NodeType::ComputeMiner,
+        CliNodeType::StorageMiner => NodeType::StorageMiner,
+    };
+    
+    log::info!("📤 Preparing transaction to register node...");
+    
+    // Create the transaction with HIPS key as first parameter
+    let tx = custom_runtime::tx().registration().register_node_with_hotkey(
+        hips_account,  // HIPS key as AccountId32
+        runtime_node_type,
+        node_id.clone().into_bytes(),
+        pay_in_credits,
+        ipfs_node_id.map(|id| id.into_bytes())
+    );
+
+    // Sign with the hotkey
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
+    
+    log::info!("✅ Successfully registered node!");
+    log::info!("📦 Node ID: {}", node_id);
+
+    Ok(())
+}
+
+/// Returns the node and IPFS IDs to register, detecting the ones left out on the
+/// command line when `auto_detect` is set. Miners need an IPFS ID; validators do not.
+async fn resolve_node_ids(
+    opts: &GlobalOpts,
+    node_type: CliNodeType,
+    node_id: Option<String>,
+    ipfs_node_id: Option<String>,
+    auto_detect: bool,
+) -> Result<(String, Option<String>), CliError> {
+    let node_id = match node_id {
+        Some(node_id) => node_id,
+        None if auto_detect => {
+            let node_id = detect_node_id(opts)
+                .await
+                .map_err(|e| format!("could not auto-detect the node ID: {}; pass --node-id", e))?;
+            log::info!("🔎 Detected node ID: {}", node_id);
+            node_id
+        }
+        None => return Err(CliError::Invalid("--node-id is required unless --auto-detect is given".to_string())),
+    };
+
+    let ipfs_node_id = match ipfs_node_id {
+        None if auto_detect && node_type != CliNodeType::Validator => {
+            let ipfs_node_id = detect_ipfs_node_id(None).await.map_err(|e| {
+                format!("could not auto-detect the IPFS node ID, which {:?} nodes require: {}; pass --ipfs-node-id", node_type, e)
+            })?;
+            log::info!("🔎 Detected IPFS node ID: {}", ipfs_node_id);
+            Some(ipfs_node_id)
+        }
+        ipfs_node_id => ipfs_node_id,
+    };
+
+    validate_peer_id("--node-id", &node_id)?;
+    if let Some(ipfs_node_id) = &ipfs_node_id {
+        validate_peer_id("--ipfs-node-id", ipfs_node_id)?;
+    }
+
+    Ok((node_id, ipfs_node_id))
+}
+
+/// Miners serve data over IPFS, so their registration must carry an IPFS node ID.
+fn require_ipfs_node_id(node_type: CliNodeType, ipfs_node_id: Option<&str>) -> Result<(), CliError> {
+    match (node_type, ipfs_node_id) {
+        (CliNodeType::ComputeMiner | CliNodeType::StorageMiner, None) => Err(CliError::Invalid(format!(
+            "--ipfs-node-id is required for {:?} nodes; pass it or use --auto-detect to read it from the local IPFS daemon",
+            node_type
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Longest node ID accepted. The runtime stores IDs as unbounded `Vec<u8>`, so
+/// this only keeps junk off the chain; real peer IDs are around 52 characters.
+const MAX_NODE_ID_LEN: usize = 128;
+
+/// Checks that `id` looks like a libp2p peer ID: a base58 multihash (`12D3KooW...`
+/// or `Qm...`) or a CIDv1 with the `libp2p-key` codec.
+fn validate_peer_id(flag: &str, id: &str) -> Result<(), CliError> {
+    let invalid = |reason: &str| CliError::Invalid(format!("{} '{}' is not a valid peer ID: {}", flag, id, reason));
+    if id.is_empty() {
+        return Err(CliError::Invalid(format!("{} must not be empty", flag)));
+    }
+    if id.len() > MAX_NODE_ID_LEN {
+        return Err(invalid(&format!("longer than {} characters", MAX_NODE_ID_LEN)));
+    }
+
+    const LIBP2P_KEY_CODEC: u64 = 0x72;
+    if let Ok(cid) = cid::Cid::try_from(id) {
+        if cid.version() == cid::Version::V1 {
+            return if cid.codec() == LIBP2P_KEY_CODEC { Ok(()) } else { Err(invalid("CID is not a libp2p-key")) };
+        }
+    }
+    let bytes = cid::multibase::Base::Base58Btc.decode(id).map_err(|_| invalid("not base58 encoded"))?;
+    let hash = cid::multihash::Multihash::<64>::from_bytes(&bytes).map_err(|_| invalid("not a multihash"))?;
+    // Identity hashes inline small public keys (ed25519); larger keys are sha2-256 hashed
+    match hash.code() {
+        0x00 | 0x12 => Ok(()),
+        code => Err(invalid(&format!("unexpected multihash code {:#x}", code))),
+    }
+}
+
+async fn handle_register_node_with_coldkey(opts: &GlobalOpts, node_type: CliNodeType, node_id: String, pay_in_credits: bool, ipfs_node_id: Option<String>) -> Result<(), CliError> {
+    log::info!("🚀 Initializing Node Registration for: {} ", node_id);
+    require_ipfs_node_id(node_type, ipfs_node_id.as_deref())?;
+    
+    let (api, signer) = setup_substrate_client(opts).await?;
+    
+    // Convert CliNodeType to runtime NodeType
+    let runtime_node_type = match node_type {
+        CliNodeType::Validator => NodeType::Validator,
+        CliNodeType::ComputeMiner => NodeType::ComputeMiner,
+        CliNodeType::StorageMiner => NodeType::StorageMiner,
+    };
+    
+    log::info!("📤 Submitting transaction to register node...");
+    let tx = custom_runtime::tx().registration().register_node_with_coldkey(runtime_node_type, node_id.clone().into_bytes(), pay_in_credits, ipfs_node_id.map(|id| id.into_bytes()));
+
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
+    
+    log::info!("✅ Successfully registered node!");
+    log::info!("📦 Node ID: {}", node_id);
+
+    Ok(())
+}
+
+async fn handle_generate_keys(opts: &GlobalOpts, out_dir: Option<String>, scheme: KeyScheme, word_count: usize) -> Result<(), CliError> {
+    let keypair_dir = match out_dir {
+        Some(dir) => dir,
+        None => home_dir()
+            .ok_or("Could not find home directory")?
+            .join("hippius/keystore")
+            .display()
+            .to_string(),
+    };
+
+    let KeypairFiles { address, public_key, mnemonic, seed, public_key_path, mnemonic_path, seed_path } =
+        write_keypair_files(Path::new(&keypair_dir), scheme, word_count)?;
+
+    if opts.is_json() {
+        return print_json(opts, &GeneratedKey {
+            address,
+            scheme,
+            public_key_hex: hex::encode(public_key),
+            mnemonic: Some(mnemonic),
+            keystore_path: keypair_dir,
+            proxy_tx_hash: None,
+        });
+    }
+
+    println!("🔑 Keypair Generated Successfully!");
+    println!("🔐 Scheme: {}", scheme.name());
+    println!("📍 Address: {}", address);
+    println!("📝 Mnemonic: {}", mnemonic);
+    println!("📝 Secret Seed: 0x{}", hex::encode(seed));
+    println!("⚠️ WARNING: Store this mnemonic safely! It recovers this key, and anyone holding it controls it.");
+    println!("📁 Keypair Directory: {}", keypair_dir);
+    println!("📄 Public Key Path: {}", public_key_path.display());
+    println!("📄 Mnemonic Path: {}", mnemonic_path.display());
+    println!("📄 Seed Path: {}", seed_path.display());
+
+    Ok(())
+}
+
+/// A keypair written to disk by `write_keypair_files`
+struct KeypairFiles {
+    /// SS58 address of the account the key controls
+    address: String,
+    public_key: Vec<u8>,
+    mnemonic: String,
+    /// Mini-secret key seed derived from `mnemonic`
+    seed: [u8; 32],
+    public_key_path: std::path::PathBuf,
+    mnemonic_path: std::path::PathBuf,
+    seed_path: std::path::PathBuf,
+}
+
+/// Generates a new `scheme` keypair from a fresh BIP39 mnemonic and writes
+/// `public_key.ss58`, `mnemonic.txt` and the raw 32-byte `seed.bin` (both
+/// secrets owner-only on Unix) into `keypair_dir`. Existing files are never replaced.
+fn write_keypair_files(keypair_dir: &Path, scheme: KeyScheme, word_count: usize) -> Result<KeypairFiles, CliError> {
+    // Ensure directory exists
+    fs::create_dir_all(keypair_dir)?;
+
+    // Standard Substrate derivation from the mnemonic's entropy, as `--seed-file`,
+    // subkey and polkadot.js use, so the mnemonic and seed.bin are the same key
+    let mnemonic = generate_mnemonic(word_count);
+    let phrase_error = |e| format!("Failed to derive key from mnemonic: {:?}", e);
+
+    let (address, public_key, seed) = match scheme {
+        KeyScheme::Sr25519 => {
+            let (pair, seed) = sr25519::Pair::from_phrase(&mnemonic, None).map_err(phrase_error)?;
+            (pair.public().to_ss58check(), pair.public().0.to_vec(), seed)
+        }
+        KeyScheme::Ed25519 => {
+            let (pair, seed) = ed25519::Pair::from_phrase(&mnemonic, None).map_err(phrase_error)?;
+            (pair.public().to_ss58check(), pair.public().0.to_vec(), seed)
+        }
+        KeyScheme::Ecdsa => {
+            // An ecdsa account is the blake2-256 hash of the 33-byte compressed public key
+            let (pair, seed) = ecdsa::Pair::from_phrase(&mnemonic, None).map_err(phrase_error)?;
+            let account = sp_core::crypto::AccountId32::from(sp_core::blake2_256(&pair.public().0));
+            (account.to_ss58check(), pair.public().0.to_vec(), seed)
+        }
+    };
+
+    // Prepare file paths
+    let public_key_path = keypair_dir.join("public_key.ss58");
+    let mnemonic_path = keypair_dir.join("mnemonic.txt");
+    let seed_path = keypair_dir.join("seed.bin");
+
+    // These may be the only backup of an earlier key, so never replace them
+    for path in [&public_key_path, &mnemonic_path, &seed_path] {
+        if path.exists() {
+            return Err(format!("A keypair file already exists at {}; move it away or pass another --out-dir", path.display()).into());
+        }
+    }
+
+    // Write public key, mnemonic and seed to files
+    fs::write(&public_key_path, &address)?;
+    write_secret_file(&mnemonic_path, mnemonic.as_bytes())?;
+    write_secret_file(&seed_path, &seed)?; // Save seed as raw binary
+
+    Ok(KeypairFiles { address, public_key, mnemonic, seed, public_key_path, mnemonic_path, seed_path })
+}
+
+/// The `Credits::LockedCredits` entries of `account`
+async fn fetch_locked_credits(api: &OnlineClient<PolkadotConfig>, account: &AccountId32) -> Result<Vec<LockedCredit<AccountId32, u32>>, CliError> {
+    let storage_query = subxt::dynamic::storage("Credits", "LockedCredits", vec![
+        subxt::dynamic::Value::from_bytes(account.encode())
+    ]);
+
+    match api.storage().at_latest().await?.fetch(&storage_query).await? {
+        Some(credits_value) => Ok(credits_value.as_type()?),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Marks one of `account`'s locked credits as fulfilled. The Credits pallet
+/// only lets its authorities do this, so the signer is checked up front.
+async fn handle_fulfill_locked_credit(opts: &GlobalOpts, lock_id: u64, account: &str, tx_hash: &str) -> Result<(), CliError> {
+    let account = resolve_account(opts, account)?;
+    let (api, signer) = setup_substrate_client(opts).await?;
+    let storage = api.storage().at_latest().await?;
+
+    let authorities: Vec<AccountId32> = match storage.fetch(&subxt::dynamic::storage("Credits", "Authorities", vec![])).await? {
+        Some(value) => value.as_type()?,
+        None => Vec::new(),
+    };
+    if !authorities.contains(signer.account_id()) {
+        return Err(CliError::Invalid(format!(
+            "{} is not a Credits authority; only authorities can fulfill locked credits",
+            signer.account_id()
+        )));
+    }
+
+    let locked_credits = fetch_locked_credits(&api, &account).await?;
+    let credit = locked_credits
+        .iter()
+        .find(|credit| credit.id == lock_id)
+        .ok_or_else(|| CliError::Invalid(format!("{} has no locked credit with ID {}", account, lock_id)))?;
+    if credit.is_fulfilled {
+        return Err(CliError::Invalid(format!("locked credit {} of {} is already fulfilled", lock_id, account)));
+    }
+
+    log::info!("🔓 Fulfilling locked credit {} of {} ({} credits)", lock_id, account, credit.amount_locked);
+    if !opts.dry_run && !confirm(opts, &format!("Mark locked credit {} of {} as fulfilled by {}?", lock_id, account, tx_hash))? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
+
+    let tx = custom_runtime::tx().credits().fulfill_locked_credits(lock_id, account.clone(), tx_hash.as_bytes().to_vec());
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
+
+    log::info!("✅ Locked credit {} of {} is fulfilled", lock_id, account);
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct LockedCreditsOutput {
+    locked_credits: Vec<LockedCreditOutput>,
+    total_locked: u128,
+}
+
+#[derive(Serialize, Default)]
+struct LockedCreditOutput {
+    id: u64,
+    amount_locked: u128,
+    created_at: u32,
+    is_fulfilled: bool,
+    tx_hash: Option<String>,
+}
+
+async fn handle_list_locked_credits(opts: &GlobalOpts) -> Result<(), CliError> {
+    opts.status("🔒 Fetching Locked Credits...");
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+
+    let locked_credits = fetch_locked_credits(&api, signer.account_id()).await?;
+
+    let total_locked: u128 = locked_credits.iter().map(|c| c.amount_locked).sum();
+
+    if opts.is_json() || opts.output == OutputFormat::Csv {
+        let rows: Vec<LockedCreditOutput> = locked_credits
+            .iter()
+            .map(|credit| LockedCreditOutput {
+                id: credit.id,
+                amount_locked: credit.amount_locked,
+                created_at: credit.created_at,
+                is_fulfilled: credit.is_fulfilled,
+                tx_hash: credit.tx_hash.as_deref().map(bytes_to_display),
+            })
+            .collect();
+        if opts.output == OutputFormat::Csv {
+            return print_csv(&rows);
+        }
+        return print_json(opts, &LockedCreditsOutput { locked_credits: rows, total_locked });
+    }
+
+    if locked_credits.is_empty() {
+        println!("❌ No locked credits found for your account.");
+        return Ok(());
+    }
+
+    println!("🏦 Locked Credits:");
+    println!("------------------------");
+    for (index, credit) in locked_credits.iter().enumerate() {
+        println!("Lock #{}", index + 1);
+        println!("  Amount Locked: {}", credit.amount_locked);
+        println!("  Created At Block: {}", credit.created_at);
+        println!("  Lock ID: {}", credit.id);
+        println!("  Fulfilled: {}", credit.is_fulfilled);
+        if let Some(tx_hash) = &credit.tx_hash {
+            println!("  Transaction Hash: {}", bytes_to_display(tx_hash));
+        }
+        println!("------------------------");
+    }
+
+    println!("💰 Total Locked Credits: {}", total_locked);
+
+    Ok(())
+}
+
+/// One row of a CSV manifest
+struct ManifestRow {
+    /// 1-based line in the CSV, for error messages
+    line: u64,
+    file_hash: String,
+    file_name: String,
+    /// Miners to pin this file on; `None` lets the network choose
+    miner_ids: Option<Vec<Vec<u8>>>,
+}
 
+/// Reads a CSV manifest. Columns are found by header name: `file_hash` (or
+/// `cid`), `file_name` (or `name`) and an optional `miner_ids` holding a
+/// comma-separated list. Without recognised headers the columns are taken
+/// in that order.
+fn read_file_manifest(csv_path: &str) -> Result<Vec<ManifestRow>, CliError> {
     // Validate CSV file exists
     if !Path::new(csv_path).exists() {
         return Err(format!("CSV file not found: {}", csv_path).into());
@@ -1769,125 +5651,934 @@ async fn handle_bulk_upload(csv_path: &str) -> Result<(), Box<dyn std::error::Er
     // Create a CSV reader
     let mut rdr = ReaderBuilder::new()
         .has_headers(true)
+        .flexible(true)
         .from_path(csv_path)?;
 
-    // Prepare file inputs
-    let mut file_inputs = Vec::new();
+    let headers = rdr.headers()?.clone();
+    let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.trim().to_lowercase().as_str()));
+    let (hash_col, name_col, miners_col) = match (column(&["file_hash", "cid", "file_cid"]), column(&["file_name", "name"])) {
+        (Some(hash_col), Some(name_col)) => (hash_col, name_col, column(&["miner_ids"])),
+        _ => (0, 1, (headers.len() > 2).then_some(2)),
+    };
+
+    let mut entries = Vec::new();
 
     // Iterate through CSV records
     for result in rdr.records() {
         let record = result?;
+        let line = record.position().map_or(0, |p| p.line());
+
+        let (Some(file_hash), Some(file_name)) = (record.get(hash_col), record.get(name_col)) else {
+            return Err(format!("line {}: expected a file CID and a file name", line).into());
+        };
+        let miner_ids = miners_col
+            .and_then(|col| record.get(col))
+            .map(|ids| {
+                ids.split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(|id| id.as_bytes().to_vec())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|ids| !ids.is_empty());
+
+        entries.push(ManifestRow {
+            line,
+            file_hash: file_hash.trim().to_string(),
+            file_name: file_name.trim().to_string(),
+            miner_ids,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Reads a single-column list of CIDs. A `cid`/`file_hash` header row and blank
+/// lines are skipped, so a plain newline-delimited file works too.
+fn read_cid_list(csv_path: &str) -> Result<Vec<String>, CliError> {
+    // Validate CSV file exists
+    if !Path::new(csv_path).exists() {
+        return Err(format!("CSV file not found: {}", csv_path).into());
+    }
+
+    let mut rdr = ReaderBuilder::new()
+        .has_headers(false)
+        .from_path(csv_path)?;
+
+    let mut cids = Vec::new();
+    for (index, result) in rdr.records().enumerate() {
+        let record = result?;
+        if record.len() != 1 {
+            return Err("CSV must have exactly one column: file CID".into());
+        }
+
+        let cid = record[0].trim();
+        let is_header = index == 0 && ["cid", "file_cid", "file_hash"].contains(&cid.to_lowercase().as_str());
+        if !cid.is_empty() && !is_header {
+            cids.push(cid.to_string());
+        }
+    }
+
+    Ok(cids)
+}
+
+/// Unpins every CID in `csv_path` with one `utility.force_batch`, so a bad CID
+/// does not stop the rest, then reports each CID's outcome.
+async fn handle_unpin_bulk(
+    opts: &GlobalOpts,
+    api: &OnlineClient<PolkadotConfig>,
+    signer: &PairSigner<PolkadotConfig, sr25519::Pair>,
+    csv_path: &str,
+) -> Result<(), CliError> {
+    let cids = read_cid_list(csv_path)?;
+    if cids.is_empty() {
+        log::warn!("⚠️ No CIDs found in the CSV to unpin.");
+        return Ok(());
+    }
+
+    let calls = cids
+        .iter()
+        .map(|cid| RuntimeCall::Marketplace(MarketplaceCall::storage_unpin_request { file_hash: cid.as_bytes().to_vec() }))
+        .collect();
+    let tx = custom_runtime::tx().utility().force_batch(calls);
+
+    if !opts.dry_run && !confirm(opts, &format!("Unpin {} files listed in {}?", cids.len(), csv_path))? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
+
+    log::info!("🔓 Submitting one batched transaction to unpin {} files...", cids.len());
+    let Some(events) = submit_and_wait(opts, api, &tx, signer).await? else {
+        return Ok(());
+    };
+
+    // force_batch emits exactly one ItemCompleted or ItemFailed per call, in order
+    let mut outcomes = Vec::new();
+    for ev in events.iter() {
+        let ev = ev?;
+        if let Some(failed) = ev.as_event::<custom_runtime::utility::events::ItemFailed>()? {
+            outcomes.push(Err(format!("{:?}", failed.error)));
+        } else if ev.as_event::<custom_runtime::utility::events::ItemCompleted>()?.is_some() {
+            outcomes.push(Ok(()));
+        }
+    }
+
+    let failed = outcomes.iter().filter(|outcome| outcome.is_err()).count();
+    println!("\n📋 Unpin results:");
+    for (cid, outcome) in cids.iter().zip(&outcomes) {
+        match outcome {
+            Ok(()) => println!("  ✅ {}", cid),
+            Err(e) => println!("  ❌ {}: {}", cid, e),
+        }
+    }
+    println!("\n{} unpinned, {} failed", outcomes.len() - failed, failed);
+
+    if failed > 0 {
+        return Err(format!("{} of {} unpin requests failed", failed, cids.len()).into());
+    }
+    Ok(())
+}
+
+/// Checks that `cid` is a CIDv0 (base58 `Qm...`) or a base32 CIDv1 (`bafy...`).
+fn validate_cid(cid: &str) -> Result<(), CliError> {
+    let invalid = |reason: String| CliError::InvalidCid { cid: cid.to_string(), reason };
+    let parsed = cid::Cid::try_from(cid).map_err(|e| invalid(e.to_string()))?;
+    if parsed.version() == cid::Version::V1 && !cid.starts_with('b') {
+        return Err(invalid("CIDv1 must be base32 encoded (starting with 'b')".to_string()));
+    }
+    Ok(())
+}
+
+/// One operation of a `batch` file, e.g. `{"op": "transfer", "to": "5F...", "amount": 1000}`
+#[derive(serde::Deserialize)]
+#[serde(tag = "op", rename_all = "kebab-case")]
+enum BatchOp {
+    Transfer {
+        to: String,
+        #[serde(deserialize_with = "deserialize_amount")]
+        amount: u128,
+    },
+    Pin { cid: String, file_name: String, miner_ids: Option<Vec<String>> },
+    Unpin { cid: String },
+    Stake {
+        #[serde(deserialize_with = "deserialize_amount")]
+        amount: u128,
+    },
+    Unstake {
+        #[serde(deserialize_with = "deserialize_amount")]
+        amount: u128,
+    },
+}
+
+/// Reads an amount given as a JSON number or, for values past u64, as a
+/// decimal string (tagged enums buffer numbers, which loses u128 support).
+fn deserialize_amount<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        Number(u64),
+        Text(String),
+    }
+    match <Amount as serde::Deserialize>::deserialize(deserializer)? {
+        Amount::Number(amount) => Ok(amount.into()),
+        Amount::Text(amount) => amount.parse().map_err(|_| serde::de::Error::custom(format!("invalid amount {:?}", amount))),
+    }
+}
+
+impl BatchOp {
+    /// Builds the operation's call, checking its accounts and CIDs.
+    fn call(&self, opts: &GlobalOpts) -> Result<RuntimeCall, CliError> {
+        Ok(match self {
+            BatchOp::Transfer { to, amount } => RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+                dest: MultiAddress::Id(resolve_account(opts, to)?),
+                value: *amount,
+            }),
+            BatchOp::Pin { cid, file_name, miner_ids } => {
+                validate_cid(cid)?;
+                RuntimeCall::Marketplace(MarketplaceCall::storage_request {
+                    files_input: vec![FileInput { file_hash: cid.as_bytes().to_vec(), file_name: file_name.as_bytes().to_vec() }],
+                    miner_ids: miner_id_bytes(miner_ids.as_deref()),
+                })
+            }
+            BatchOp::Unpin { cid } => {
+                validate_cid(cid)?;
+                RuntimeCall::Marketplace(MarketplaceCall::storage_unpin_request { file_hash: cid.as_bytes().to_vec() })
+            }
+            BatchOp::Stake { amount } => RuntimeCall::Staking(StakingCall::bond { value: *amount, payee: Staked }),
+            BatchOp::Unstake { amount } => RuntimeCall::Staking(StakingCall::unbond { value: *amount }),
+        })
+    }
+
+    /// Pallet of the operation's call, to match a failed batch against
+    fn pallet(&self) -> &'static str {
+        match self {
+            BatchOp::Transfer { .. } => "Balances",
+            BatchOp::Pin { .. } | BatchOp::Unpin { .. } => "Marketplace",
+            BatchOp::Stake { .. } | BatchOp::Unstake { .. } => "Staking",
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            BatchOp::Transfer { to, amount } => format!("transfer {} to {}", amount, to),
+            BatchOp::Pin { cid, file_name, .. } => format!("pin {} as {}", cid, file_name),
+            BatchOp::Unpin { cid } => format!("unpin {}", cid),
+            BatchOp::Stake { amount } => format!("stake {}", amount),
+            BatchOp::Unstake { amount } => format!("unstake {}", amount),
+        }
+    }
+}
+
+/// Submits every operation in `path` as one `utility.batch_all`. The batch
+/// reverts as a whole on the first failing call; since the runtime does not
+/// say which one that was, the operations of the failing pallet are named.
+async fn handle_batch(opts: &GlobalOpts, path: &str) -> Result<(), CliError> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("could not read batch file {}: {}", path, e))?;
+    let ops: Vec<BatchOp> = serde_json::from_str(&contents)
+        .map_err(|e| CliError::Invalid(format!("{} is not a valid batch file: {}", path, e)))?;
+    if ops.is_empty() {
+        return Err(CliError::Invalid(format!("{} lists no operations", path)));
+    }
+
+    // Built before connecting so a bad account or CID fails fast
+    let calls = ops
+        .iter()
+        .enumerate()
+        .map(|(index, op)| op.call(opts).map_err(|e| CliError::Invalid(format!("operation #{} ({}): {}", index + 1, op.describe(), e))))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+
+    log::info!("📋 Batch of {} operations:", ops.len());
+    for (index, op) in ops.iter().enumerate() {
+        log::info!("  #{} {}", index + 1, op.describe());
+    }
+    if !opts.dry_run && !confirm(opts, &format!("Submit these {} operations as one transaction?", ops.len()))? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
+
+    let tx = custom_runtime::tx().utility().batch_all(calls);
+    match submit_and_wait(opts, &api, &tx, &signer).await {
+        Ok(Some(_)) => {
+            log::info!("✅ All {} operations succeeded", ops.len());
+            Ok(())
+        }
+        Ok(None) => Ok(()),
+        Err(CliError::TxFailed { message, pallet: Some(pallet) }) => {
+            let suspects: Vec<String> = ops
+                .iter()
+                .enumerate()
+                .filter(|(_, op)| op.pallet() == pallet)
+                .map(|(index, op)| format!("#{} ({})", index + 1, op.describe()))
+                .collect();
+            let culprit = match suspects.as_slice() {
+                [] => String::new(),
+                [only] => format!("; operation {} failed", only),
+                _ => format!("; one of operations {} failed", suspects.join(", ")),
+            };
+            Err(CliError::TxFailed { message: format!("{}{}; no operation was applied", message, culprit), pallet: Some(pallet) })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Pins every row of the CSV, at most `batch_size` files per `storage_request` so
+/// large manifests stay under the extrinsic size limit. A failed batch does not stop
+/// the rest; its rows are listed at the end for a retry.
+async fn handle_bulk_upload(opts: &GlobalOpts, csv_path: &str, skip_invalid: bool, batch_size: usize, replicas: Option<u32>) -> Result<(), CliError> {
+    log::info!("🗄️ Initiating Bulk File Upload from CSV: {}", csv_path);
+
+    let mut rows = Vec::new();
+    let mut invalid = 0;
+    for row in read_file_manifest(csv_path)? {
+        if let Err(e) = validate_cid(&row.file_hash) {
+            invalid += 1;
+            log::warn!("❌ Line {}: {}", row.line, e);
+            continue;
+        }
+        rows.push(row);
+    }
+    if invalid > 0 {
+        if !skip_invalid {
+            return Err(CliError::Invalid(format!("{} rows have an invalid CID; fix them or pass --skip-invalid", invalid)));
+        }
+        log::warn!("⚠️ Skipping {} rows with an invalid CID", invalid);
+    }
+
+    if rows.is_empty() {
+        log::warn!("⚠️ No files found in the CSV to upload.");
+        return Ok(());
+    }
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+
+    // Rows that name their own miners keep them
+    if let Some(replicas) = replicas {
+        let miner_ids = select_replica_miners(&api, replicas).await?;
+        for row in rows.iter_mut().filter(|row| row.miner_ids.is_none()) {
+            row.miner_ids = Some(miner_ids.clone());
+        }
+    }
+
+    let total = rows.len();
+    let mut pinned = 0;
+    // (CSV line, CID, error) for every row of a failed batch
+    let mut failed: Vec<(u64, String, String)> = Vec::new();
+
+    // A storage_request takes one miner list for all its files, so a batch also
+    // ends wherever the requested miners change
+    let mut batches = Vec::new();
+    let mut start = 0;
+    for end in 1..=rows.len() {
+        if end == rows.len() || end - start == batch_size || rows[end].miner_ids != rows[start].miner_ids {
+            batches.push(&rows[start..end]);
+            start = end;
+        }
+    }
+
+    for (batch_index, batch) in batches.into_iter().enumerate() {
+        let file_inputs: Vec<FileInput> = batch
+            .iter()
+            .map(|row| FileInput {
+                file_hash: row.file_hash.clone().into_bytes(),
+                file_name: row.file_name.clone().into_bytes(),
+            })
+            .collect();
+
+        log::info!("📌 Submitting batch {} ({} files)...", batch_index + 1, batch.len());
+        let tx = custom_runtime::tx()
+            .marketplace()
+            .storage_request(file_inputs, batch[0].miner_ids.clone());
+
+        match submit_and_wait(opts, &api, &tx, &signer).await {
+            Ok(Some(_)) => pinned += batch.len(),
+            // Dry run: nothing was submitted
+            Ok(None) => continue,
+            Err(e) => {
+                log::warn!("❌ Batch {} failed: {}", batch_index + 1, e);
+                for row in batch {
+                    failed.push((row.line, row.file_hash.clone(), e.to_string()));
+                }
+            }
+        }
+        log::info!("⏳ {}/{} pinned", pinned, total);
+    }
+
+    if failed.is_empty() {
+        if !opts.dry_run {
+            log::info!("✅ Successfully pinned all {} files!", total);
+        }
+        return Ok(());
+    }
+
+    println!("\n❌ {} rows were not pinned:", failed.len());
+    for (line, file_hash, error) in &failed {
+        println!("  line {}: {} ({})", line, file_hash, error);
+    }
+    Err(format!("{} of {} files failed to pin", failed.len(), total).into())
+}
+
+#[derive(Serialize)]
+struct PlansOutput {
+    plans: Vec<PlanOutput>,
+}
+
+#[derive(Serialize, Default)]
+struct PlanOutput {
+    id: String,
+    name: String,
+    description: String,
+    technical_description: String,
+    price: u128,
+    is_suspended: bool,
+}
+
+impl From<&Plan<H256>> for PlanOutput {
+    fn from(plan: &Plan<H256>) -> Self {
+        // Convert byte vectors to strings for display
+        PlanOutput {
+            id: format!("{:?}", plan.id),
+            name: bytes_to_display(&plan.plan_name),
+            description: bytes_to_display(&plan.plan_description),
+            technical_description: bytes_to_display(&plan.plan_technical_description),
+            price: plan.price,
+            is_suspended: plan.is_suspended,
+        }
+    }
+}
+
+fn print_plan(plan: &PlanOutput) {
+    println!("Plan Details:");
+    println!("  ID: {}", plan.id);
+    println!("  Name: {}", plan.name);
+    println!("  Description: {}", plan.description);
+    println!("  Technical Description: {}", plan.technical_description);
+    println!("  Price: {} tokens", plan.price);
+    println!("  Suspended: {}", if plan.is_suspended { "Yes" } else { "No" });
+    println!("---");
+}
+
+/// Fetches one plan by key from `Marketplace::Plans`. A suspended plan is
+/// still shown, but the command fails since it cannot be purchased.
+async fn handle_show_plan(opts: &GlobalOpts, plan_id: H256) -> Result<(), CliError> {
+    opts.status(format!("📋 Fetching Marketplace Plan {:?}", plan_id));
+
+    let api = connect_any(opts).await?;
+    let storage_query = subxt::dynamic::storage("Marketplace", "Plans", vec![subxt::dynamic::Value::from_bytes(plan_id)]);
+    let plan: Plan<H256> = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&storage_query)
+        .await?
+        .ok_or_else(|| CliError::Invalid(format!("no plan with ID {:?} in the marketplace", plan_id)))?
+        .as_type()?;
+    let plan = PlanOutput::from(&plan);
+
+    if opts.is_json() {
+        print_json(opts, &plan)?;
+    } else {
+        print_plan(&plan);
+    }
+    if plan.is_suspended {
+        return Err(CliError::Other(format!("plan {} is suspended and cannot be purchased", plan.id)));
+    }
+    Ok(())
+}
+
+async fn handle_list_plans(opts: &GlobalOpts) -> Result<(), CliError> {
+    opts.status("📋 Fetching Available Marketplace Plans");
+
+    let api = connect_any(opts).await?;
+
+    // Build a dynamic storage query for plans
+    let storage_query = subxt::dynamic::storage("Marketplace", "Plans", vec![]);
+    
+    // Fetch storage entries
+    let mut results = api.storage().at_latest().await?.iter(storage_query).await?;
+    
+    let mut plans = Vec::new();
+    
+    // Iterate through results
+    while let Some(Ok(kv)) = results.next().await {
+        // Decode the plan from the value
+        let plan: Plan<H256> = kv.value.as_type()?;
         
-        // Assume CSV has two columns: file CID and file name
-        if record.len() != 2 {
-            return Err("CSV must have exactly two columns: file CID and file name".into());
+        plans.push(PlanOutput::from(&plan));
+    }
+
+    if opts.is_json() {
+        return print_json(opts, &PlansOutput { plans });
+    }
+    if opts.output == OutputFormat::Csv {
+        return print_csv(&plans);
+    }
+
+    for plan in &plans {
+        print_plan(plan);
+    }
+
+    if plans.is_empty() {
+        println!("⚠️ No plans found in the marketplace.");
+    } else {
+        println!("✅ Total Plans Found: {}", plans.len());
+    }
+
+    Ok(())
+}
+
+/// Transfers the whole balance of every local wallet holding more than
+/// `threshold` (default: the transfer fee) to `dest`. Never prompts: it refuses
+/// to submit without `--yes`, and `--dry-run` previews the list instead.
+async fn handle_dust_sweep(opts: &GlobalOpts, dest: &str, threshold: Option<u128>) -> Result<(), CliError> {
+    let dest = resolve_account(opts, dest)?;
+    log::info!("🧹 Sweeping local wallets into: {}", dest);
+
+    let api = connect_any(opts).await?;
+    let tx = custom_runtime::tx()
+        .balances()
+        .transfer_all(MultiAddress::Id(dest.clone()), false);
+
+    let mut candidates = Vec::new();
+    for (label, pair) in load_local_wallets(opts)? {
+        let account_id: AccountId32 = pair.public().into();
+        if account_id == dest {
+            continue;
+        }
+
+        let free = query_free_balance(&api, &account_id).await?;
+        let signer = PairSigner::new(pair);
+        let fee = api
+            .tx()
+            .create_signed(&tx, &signer, Default::default())
+            .await?
+            .partial_fee_estimate()
+            .await?;
+        let min_balance = threshold.unwrap_or(fee);
+        if free <= min_balance {
+            log::info!("⏭️  {} ({}): {} is not above {}, skipping", label, account_id, free, min_balance);
+            continue;
+        }
+
+        log::info!("🔎 {} ({}): would sweep {} (fee ~{})", label, account_id, free, fee);
+        candidates.push((label, account_id, signer, free));
+    }
+
+    if candidates.is_empty() {
+        log::info!("ℹ️ No wallet has a balance worth sweeping");
+        return Ok(());
+    }
+    let total: u128 = candidates.iter().map(|(_, _, _, free)| free).sum();
+    // It empties many accounts at once, so a stray "y" at a prompt is not enough
+    if !opts.dry_run && !opts.yes {
+        return Err(CliError::Invalid(format!(
+            "refusing to sweep {} from {} wallet(s) into {} without --yes; check the list with --dry-run first",
+            total, candidates.len(), dest
+        )));
+    }
+
+    let mut swept = 0u128;
+    let mut swept_wallets = 0;
+    for (label, account_id, signer, free) in candidates {
+        log::info!("💸 {} ({}): sweeping {}", label, account_id, free);
+        if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+            continue;
+        }
+        swept += free;
+        swept_wallets += 1;
+    }
+
+    if !opts.dry_run {
+        log::info!("✅ Swept {} from {} wallet(s) into {}", swept, swept_wallets, dest);
+    }
+    Ok(())
+}
+
+/// Loads the keypairs of every local wallet: the coldkey, if present, and all hotkeys.
+fn load_local_wallets(opts: &GlobalOpts) -> Result<Vec<(String, sr25519::Pair)>, CliError> {
+    let mut wallets = Vec::new();
+
+    let keystore_path = opts.keystore_path();
+    if let Some(file_name) = find_hips_key(&keystore_path).ok().flatten() {
+        // The node keystore stores the secret phrase as a JSON string
+        let phrase: String = serde_json::from_str(&fs::read_to_string(Path::new(&keystore_path).join(&file_name))?)?;
+        let pair = sr25519::Pair::from_string(&phrase, None)
+            .map_err(|e| format!("Failed to create pair: {:?}", e))?;
+        wallets.push((COLDKEY_LABEL.to_string(), pair));
+    }
+
+    for hotkey in find_hotkeys(&get_hotkeys_dir())? {
+        let mnemonic = read_hotkey_mnemonic(&Path::new(&get_hotkeys_dir()).join(&hotkey.label))?;
+        wallets.push((hotkey.label, hotkey_pair_from_mnemonic(&mnemonic)?));
+    }
+
+    Ok(wallets)
+}
+
+/// Fetches `System::Account` for `account`, treating a missing account as empty.
+async fn fetch_account_info(api: &OnlineClient<PolkadotConfig>, account: &AccountId32) -> Result<AccountInfo, CliError> {
+    fetch_account_info_at(&api.storage().at_latest().await?, account).await
+}
+
+/// `fetch_account_info` at the block `storage` points to
+async fn fetch_account_info_at(
+    storage: &subxt::storage::Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    account: &AccountId32,
+) -> Result<AccountInfo, CliError> {
+    let target_account = dynamic::Value::from(account.encode());
+    let balance_query = dynamic::storage("System", "Account", vec![target_account]);
+
+    match storage.fetch(&balance_query).await? {
+        Some(balance_value) => AccountInfo::decode(&mut &balance_value.encoded()[..])
+            .map_err(|e| format!("Failed to decode account info: {:?}", e).into()),
+        None => Ok(AccountInfo::default()),
+    }
+}
+
+/// Fetches the free balance of `account_id`, treating a missing account as empty.
+async fn query_free_balance(api: &OnlineClient<PolkadotConfig>, account_id: &AccountId32) -> Result<u128, CliError> {
+    Ok(fetch_account_info(api, account_id).await?.data.free)
+}
+
+#[derive(Serialize)]
+struct BalanceOutput {
+    account: String,
+    free: u128,
+    reserved: u128,
+    frozen: u128,
+    total: u128,
+}
+
+/// Shows the native token balances of `account_id`, or of the signer when none is given.
+async fn handle_balance(opts: &GlobalOpts, account_id: Option<&str>) -> Result<(), CliError> {
+    // Another account's balance is public, so a signer is only needed for our own
+    let account_id = match account_id {
+        Some(account_id) => resolve_account(opts, account_id)?,
+        None => AccountId32::from(signer_pair(opts, &opts.signer_spec())?.public().0),
+    };
+    let api = connect_any(opts).await?;
+
+    let data = fetch_account_info(&api, &account_id).await?.data;
+    let output = BalanceOutput {
+        account: account_id.to_string(),
+        free: data.free,
+        reserved: data.reserved,
+        frozen: data.frozen,
+        total: data.free.saturating_add(data.reserved),
+    };
+
+    if opts.is_json() {
+        return print_json(opts, &output);
+    }
+    println!("💰 Balance of {}", output.account);
+    println!("------------------------");
+    println!("  Free: {}", output.free);
+    println!("  Reserved: {}", output.reserved);
+    println!("  Frozen: {}", output.frozen);
+    println!("  Total: {}", output.total);
+    println!("------------------------");
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct UnlockChunkOutput {
+    value: u128,
+    era: u32,
+    withdrawable: bool,
+}
+
+#[derive(Serialize)]
+struct StakingInfoOutput {
+    stash: String,
+    controller: Option<String>,
+    active_era: Option<u32>,
+    total: u128,
+    active: u128,
+    unlocking: Vec<UnlockChunkOutput>,
+    /// Sum of the unlocking chunks whose era has been reached
+    withdrawable: u128,
+}
+
+/// Shows the staking ledger of `account_id` (the signer by default): what is
+/// bonded, what is still active, and which unbonding chunks `withdraw` can release.
+async fn handle_staking_info(opts: &GlobalOpts, account_id: Option<&str>) -> Result<(), CliError> {
+    // Staking storage is public, so a signer is only needed for our own stash
+    let stash = match account_id {
+        Some(account_id) => resolve_account(opts, account_id)?,
+        None => AccountId32::from(signer_pair(opts, &opts.signer_spec())?.public().0),
+    };
+    let api = connect_any(opts).await?;
+
+    let storage = api.storage().at_latest().await?;
+
+    // Bonded maps the stash to its controller, which keys the ledger
+    let bonded_query = subxt::dynamic::storage("Staking", "Bonded", vec![subxt::dynamic::Value::from_bytes(stash.encode())]);
+    let controller: Option<AccountId32> = match storage.fetch(&bonded_query).await? {
+        Some(value) => Some(value.as_type()?),
+        None => None,
+    };
+
+    let active_era = match storage.fetch(&subxt::dynamic::storage("Staking", "ActiveEra", vec![])).await? {
+        Some(value) => Some(value.as_type::<ActiveEraInfo>()?.index),
+        None => None,
+    };
+
+    let ledger = match &controller {
+        Some(controller) => {
+            let ledger_query = subxt::dynamic::storage("Staking", "Ledger", vec![subxt::dynamic::Value::from_bytes(controller.encode())]);
+            match storage.fetch(&ledger_query).await? {
+                Some(value) => Some(value.as_type::<StakingLedger>()?),
+                None => None,
+            }
         }
+        None => None,
+    };
 
-        let file_hash = record[0].to_string();
-        let vm_name = record[1].to_string();
+    let unlocking: Vec<UnlockChunkOutput> = ledger
+        .as_ref()
+        .map(|ledger| {
+            ledger.unlocking.0.iter().map(|chunk| UnlockChunkOutput {
+                value: chunk.value,
+                era: chunk.era,
+                withdrawable: active_era.is_some_and(|era| chunk.era <= era),
+            }).collect()
+        })
+        .unwrap_or_default();
+    let output = StakingInfoOutput {
+        stash: stash.to_string(),
+        controller: controller.as_ref().map(|c| c.to_string()),
+        active_era,
+        total: ledger.as_ref().map_or(0, |ledger| ledger.total),
+        active: ledger.as_ref().map_or(0, |ledger| ledger.active),
+        withdrawable: unlocking.iter().filter(|chunk| chunk.withdrawable).map(|chunk| chunk.value).sum(),
+        unlocking,
+    };
 
-        file_inputs.push(FileInput {
-            file_hash: file_hash.as_bytes().to_vec(),
-            file_name: vm_name.as_bytes().to_vec(),
-        });
+    if opts.is_json() {
+        return print_json(opts, &output);
     }
-
-    // Perform bulk upload
-    if !file_inputs.is_empty() {
-        let (api, signer) = setup_substrate_client().await?;
-
-        println!("📌 Submitting transaction to pin files...");
-        let tx = custom_runtime::tx()
-            .marketplace()
-            .storage_request(file_inputs, None); 
-
-        let progress = api
-            .tx()
-            .sign_and_submit_then_watch_default(&tx, &signer)
-            .await?;
-        
-        println!("⏳ Waiting for transaction to be finalized...");
-        let _ = progress.wait_for_finalized_success().await?;
-        
-        println!("✅ Successfully pinned files!");
+    println!("🥩 Staking info of {}", output.stash);
+    println!("------------------------");
+    if output.controller.is_none() {
+        println!("  Not bonded");
+        println!("------------------------");
+        return Ok(());
+    }
+    if let Some(era) = output.active_era {
+        println!("  Active era: {}", era);
+    }
+    println!("  Total bonded: {}", output.total);
+    println!("  Active: {}", output.active);
+    if output.unlocking.is_empty() {
+        println!("  Unlocking: none");
     } else {
-        println!("⚠️ No files found in the CSV to upload.");
+        println!("  Unlocking:");
+        for chunk in &output.unlocking {
+            let state = if chunk.withdrawable { "withdrawable now" } else { "locked" };
+            println!("    {} unlocks at era {} ({})", chunk.value, chunk.era, state);
+        }
     }
-
+    println!("  Withdrawable: {}", output.withdrawable);
+    println!("------------------------");
     Ok(())
 }
 
-async fn handle_list_plans() -> Result<(), Box<dyn std::error::Error>> {
-    println!("📋 Fetching Available Marketplace Plans");
-
-    let (api, _) = setup_substrate_client().await?;
+/// Blocks `account history` scans when `--from-block` is not given
+const DEFAULT_HISTORY_BLOCKS: u32 = 1000;
+
+#[derive(Serialize)]
+struct TransferOutput {
+    block: u32,
+    /// Block timestamp in milliseconds since the Unix epoch
+    timestamp: Option<u64>,
+    direction: &'static str,
+    counterparty: String,
+    amount: u128,
+}
 
-    // Build a dynamic storage query for plans
-    let storage_query = subxt::dynamic::storage("Marketplace", "Plans", vec![]);
-    
-    // Fetch storage entries
-    let mut results = api.storage().at_latest().await?.iter(storage_query).await?;
-    
-    let mut plan_count = 0;
-    
-    // Iterate through results
-    while let Some(Ok(kv)) = results.next().await {
-        // Decode the plan from the value
-        let plan: Plan<H256> = kv.value.as_type()?;
-        
-        // Convert byte vectors to strings for display
-        let plan_name = String::from_utf8_lossy(&plan.plan_name).to_string();
-        let plan_description = String::from_utf8_lossy(&plan.plan_description).to_string();
-        let plan_technical_description = String::from_utf8_lossy(&plan.plan_technical_description).to_string();
+/// Scans finalized blocks for `Balances::Transfer` events to or from `account_id`
+/// (the signer by default), oldest first.
+async fn handle_history(opts: &GlobalOpts, account_id: Option<&str>, from_block: Option<u32>, to_block: Option<u32>) -> Result<(), CliError> {
+    let account = match account_id {
+        Some(account_id) => resolve_account(opts, account_id)?,
+        None => {
+            AccountId32::from(signer_pair(opts, &opts.signer_spec())?.public().0)
+        }
+    };
 
-        // Print plan details
-        println!("Plan Details:");
-        println!("  ID: {:?}", plan.id);
-        println!("  Name: {}", plan_name);
-        println!("  Description: {}", plan_description);
-        println!("  Technical Description: {}", plan_technical_description);
-        println!("  Price: {} tokens", plan.price);
-        println!("  Suspended: {}", if plan.is_suspended { "Yes" } else { "No" });
-        println!("---");
+    let api = connect_any(opts).await?;
+    let tip = api.blocks().at_latest().await?;
+    let to_block = to_block.unwrap_or(tip.number()).min(tip.number());
+    let from_block = from_block.unwrap_or(to_block.saturating_sub(DEFAULT_HISTORY_BLOCKS - 1));
+    if from_block > to_block {
+        return Err(CliError::Invalid(format!("--from-block {} is after --to-block {}", from_block, to_block)));
+    }
+    opts.status(format!("📜 Scanning blocks {}..={} for transfers of {}", from_block, to_block, account));
 
-        plan_count += 1;
+    let mut blocks = blocks_below(&api, &tip, from_block, to_block).await?;
+    if tip.number() <= to_block {
+        blocks.push(tip);
     }
 
-    if plan_count == 0 {
-        println!("⚠️ No plans found in the marketplace.");
-    } else {
-        println!("✅ Total Plans Found: {}", plan_count);
+    let mut transfers = Vec::new();
+    for block in &blocks {
+        let events = block.events().await?;
+        let mut timestamp = None;
+        for transfer in events.find::<custom_runtime::balances::events::Transfer>() {
+            let transfer = transfer?;
+            let (direction, counterparty) = if transfer.from == account {
+                ("sent", transfer.to)
+            } else if transfer.to == account {
+                ("received", transfer.from)
+            } else {
+                continue;
+            };
+            // Only looked up for blocks that have a matching transfer
+            if timestamp.is_none() {
+                timestamp = block.storage().fetch(&custom_runtime::storage().timestamp().now()).await?;
+            }
+            transfers.push(TransferOutput {
+                block: block.number(),
+                timestamp,
+                direction,
+                counterparty: counterparty.to_string(),
+                amount: transfer.amount,
+            });
+        }
     }
 
+    if opts.is_json() {
+        return print_json(opts, &transfers);
+    }
+    if transfers.is_empty() {
+        println!("ℹ️ No transfers of {} in blocks {}..={}", account, from_block, to_block);
+        return Ok(());
+    }
+    println!("📜 Transfers of {} in blocks {}..={}", account, from_block, to_block);
+    println!("------------------------");
+    for transfer in &transfers {
+        let when = transfer.timestamp.map_or_else(|| "unknown time".to_string(), format_utc_millis);
+        let arrow = if transfer.direction == "sent" { "➡️  to" } else { "⬅️  from" };
+        println!("  #{} {} {} {} {}", transfer.block, when, arrow, transfer.counterparty, transfer.amount);
+    }
+    println!("------------------------");
     Ok(())
 }
 
-async fn handle_transfer(account_id: AccountId32, amount: u128) -> Result<(), Box<dyn std::error::Error>> {
-    println!("💸 Initiating transfer to account: {}", account_id);
+/// Formats a Unix timestamp in milliseconds as `YYYY-MM-DD HH:MM:SS UTC`.
+fn format_utc_millis(millis: u64) -> String {
+    let secs = millis / 1000;
+    let (days, rem) = ((secs / 86_400) as i64, secs % 86_400);
+    // Civil-from-days (Howard Hinnant), valid for any date after 1970
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC", year, month, day, rem / 3600, rem % 3600 / 60, rem % 60)
+}
+
+async fn handle_transfer(opts: &GlobalOpts, account_id: &str, amount: u128) -> Result<(), CliError> {
+    let account_id = resolve_account(opts, account_id)?;
+    log::info!("💸 Initiating transfer to account: {}", account_id);
     
-    let (api, signer) = setup_substrate_client().await?;
+    let (api, signer) = setup_substrate_client(opts).await?;
+
+    if !opts.dry_run && !confirm(opts, &format!("Transfer {} to {}?", amount, account_id))? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
 
     // Create the transfer transaction
     let tx = custom_runtime::tx()
         .balances()
         .transfer_keep_alive(subxt::utils::MultiAddress::Id(account_id.clone()), amount); // Specify the amount to transfer
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
     
-    println!("✅ Successfully transferred funds to account: {}", account_id);
+    log::info!("✅ Successfully transferred funds to account: {}", account_id);
+    Ok(())
+}
+
+/// Moves the signer's whole transferable balance with `balances.transfer_all`.
+async fn handle_transfer_all(opts: &GlobalOpts, account_id: &str, keep_alive: bool) -> Result<(), CliError> {
+    let account_id = resolve_account(opts, account_id)?;
+    log::info!("💸 Initiating transfer of the full balance to account: {}", account_id);
+
+    let (api, signer) = setup_substrate_client(opts).await?;
+    let sender = signer.account_id().clone();
+    let before = query_free_balance(&api, &sender).await?;
+
+    let prompt = format!(
+        "Transfer the whole balance ({}) to {}{}?",
+        before,
+        account_id,
+        if keep_alive { ", keeping the existential deposit" } else { " and empty this account" }
+    );
+    if !opts.dry_run && !confirm(opts, &prompt)? {
+        log::info!("🚫 Cancelled");
+        return Ok(());
+    }
+
+    let tx = custom_runtime::tx()
+        .balances()
+        .transfer_all(subxt::utils::MultiAddress::Id(account_id.clone()), keep_alive);
+
+    let Some(events) = submit_and_wait(opts, &api, &tx, &signer).await? else {
+        return Ok(());
+    };
+
+    let after = query_free_balance(&api, &sender).await?;
+    let transferred = events
+        .find_first::<custom_runtime::balances::events::Transfer>()?
+        .map(|transfer| transfer.amount);
+
+    log::info!("✅ Successfully transferred funds to account: {}", account_id);
+    if let Some(amount) = transferred {
+        log::info!("  Transferred: {}", amount);
+    }
+    log::info!("  Sender balance: {} -> {} (including fees)", before, after);
     Ok(())
 }
 
-async fn handle_list_ipfs_files() -> Result<(), Box<dyn std::error::Error>> {
-    println!("📦 Fetching IPFS File Hashes for Current User");
+#[derive(Serialize)]
+struct IpfsFilesOutput {
+    /// All file hashes of the account
+    total: usize,
+    /// File hashes matching `--grep` (equal to `total` without it)
+    matched: usize,
+    offset: usize,
+    /// Whether matches beyond this page were left out
+    truncated: bool,
+    file_hashes: Vec<String>,
+}
+
+/// One `list-ipfs-files --output csv` row
+#[derive(Serialize, Default)]
+struct IpfsFileRow {
+    file_hash: String,
+}
 
-    let (api, signer) = setup_substrate_client().await?;
+async fn handle_list_ipfs_files(
+    opts: &GlobalOpts,
+    diff: Option<&str>,
+    limit: Option<usize>,
+    offset: usize,
+    grep: Option<&str>,
+) -> Result<(), CliError> {
+    opts.status("📦 Fetching IPFS File Hashes for Current User");
+
+    // Read the manifest up front so a bad path fails before connecting
+    let manifest = diff.map(read_file_manifest).transpose()?;
+
+    let (api, signer) = setup_substrate_client(opts).await?;
 
     // Get the current user's account ID
     let account_id = signer.account_id();
@@ -1899,53 +6590,112 @@ async fn handle_list_ipfs_files() -> Result<(), Box<dyn std::error::Error>> {
     
     // Fetch storage entries
     let storage_client = api.storage().at_latest().await?;
-    let file_hashes_result = storage_client.fetch(&storage_query).await;
+    let file_hashes_result = storage_client.fetch(&storage_query).await?;
 
-    match file_hashes_result {
-        Ok(Some(value)) => {
-            // Decode the file hashes for the user
-            let file_hashes: Vec<Vec<u8>> = value.as_type()?;
+    let file_hashes: Vec<Vec<u8>> = match file_hashes_result {
+        Some(value) => value.as_type()?,
+        None => Vec::new(),
+    };
 
-            if file_hashes.is_empty() {
-                println!("⚠️ No file hashes found for the current user.");
-                return Ok(());
-            }
+    if let Some(manifest) = manifest {
+        return diff_against_manifest(&file_hashes, &manifest);
+    }
 
-            println!("🔢 Total File Hashes: {}", file_hashes.len());
-            
-            for (index, file_hash) in file_hashes.iter().enumerate() {
-                // Convert file hash to string for display
-                let file_hash_str = String::from_utf8_lossy(file_hash).to_string();
+    // Everything is fetched in one query; filtering and paging only shape the output
+    let total = file_hashes.len();
+    let matching: Vec<String> = file_hashes
+        .iter()
+        .map(|hash| bytes_to_display(hash))
+        .filter(|cid| grep.is_none_or(|needle| cid.contains(needle)))
+        .collect();
+    let matched = matching.len();
+    let page: Vec<String> = matching.into_iter().skip(offset).take(limit.unwrap_or(usize::MAX)).collect();
+    let truncated = offset + page.len() < matched;
 
-                println!("\n📄 File Hash #{}", index + 1);
-                println!("  {}", file_hash_str);
-            }
-        },
-        Ok(None) => {
-            println!("⚠️ No file hashes found for the current user.");
-        },
-        Err(e) => {
-            eprintln!("❌ Error fetching file hashes: {}", e);
-            return Err(e.into());
-        }
+    if opts.is_json() {
+        return print_json(opts, &IpfsFilesOutput { total, matched, offset, truncated, file_hashes: page });
+    }
+    if opts.output == OutputFormat::Csv {
+        let rows: Vec<IpfsFileRow> = page.into_iter().map(|file_hash| IpfsFileRow { file_hash }).collect();
+        return print_csv(&rows);
+    }
+
+    if total == 0 {
+        println!("⚠️ No file hashes found for the current user.");
+        return Ok(());
+    }
+
+    println!("🔢 Total File Hashes: {}", total);
+    if let Some(needle) = grep {
+        println!("🔍 Matching '{}': {}", needle, matched);
+    }
+
+    for (index, file_hash_str) in page.iter().enumerate() {
+        println!("\n📄 File Hash #{}", offset + index + 1);
+        println!("  {}", file_hash_str);
+    }
+
+    if offset >= matched && matched > 0 {
+        println!("\n⚠️ Offset {} is past the last of {} matching file hashes.", offset, matched);
+    } else if truncated && !page.is_empty() {
+        println!(
+            "\n✂️ Showing {}-{} of {}; use --offset {} for more.",
+            offset + 1,
+            offset + page.len(),
+            matched,
+            offset + page.len()
+        );
     }
 
     Ok(())
 }
 
-async fn handle_get_current_lock_period() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🕒 Fetching Current Lock Period...");
+/// Prints which manifest CIDs are not pinned on-chain and which on-chain pins
+/// are not in the manifest. Fails if anything from the manifest is missing.
+fn diff_against_manifest(file_hashes: &[Vec<u8>], manifest: &[ManifestRow]) -> Result<(), CliError> {
+    let pinned: std::collections::BTreeSet<String> = file_hashes.iter().map(|hash| bytes_to_display(hash)).collect();
+    let expected: std::collections::BTreeSet<&str> = manifest.iter().map(|row| row.file_hash.as_str()).collect();
+
+    let missing: Vec<&ManifestRow> = manifest.iter().filter(|row| !pinned.contains(&row.file_hash)).collect();
+    let extra: Vec<&String> = pinned.iter().filter(|cid| !expected.contains(cid.as_str())).collect();
+
+    println!("🔍 Manifest: {} entries, on-chain: {} pins", manifest.len(), pinned.len());
+
+    if !missing.is_empty() {
+        println!("\n❌ Missing ({} not pinned yet):", missing.len());
+        for row in &missing {
+            println!("  {}  {}", row.file_hash, row.file_name);
+        }
+    }
+
+    if !extra.is_empty() {
+        println!("\n➕ Extra ({} pinned but not in the manifest):", extra.len());
+        for cid in &extra {
+            println!("  {}", cid);
+        }
+    }
+
+    if missing.is_empty() {
+        println!("\n✅ Every manifest entry is pinned on-chain");
+        Ok(())
+    } else {
+        Err(format!("{} manifest entries are not pinned", missing.len()).into())
+    }
+}
+
+async fn handle_get_current_lock_period(opts: &GlobalOpts) -> Result<(), CliError> {
+    log::info!("🕒 Fetching Current Lock Period...");
 
-    let (api, _) = setup_substrate_client().await?;
+    let api = connect_any(opts).await?;
 
     // Build a dynamic storage query for CurrentLockPeriod
     let storage_query = subxt::dynamic::storage("Credits", "CurrentLockPeriod", vec![]);
 
     // Fetch the current lock period
-    let lock_period_result = api.storage().at_latest().await?.fetch(&storage_query).await;
+    let lock_period_result = api.storage().at_latest().await?.fetch(&storage_query).await?;
 
     match lock_period_result {
-        Ok(Some(lock_period_value)) => {
+        Some(lock_period_value) => {
             // Attempt to decode the lock period
             let lock_period: LockPeriod<u32> = lock_period_value.as_type()?;
 
@@ -1953,110 +6703,283 @@ async fn handle_get_current_lock_period() -> Result<(), Box<dyn std::error::Erro
             println!("  Start Block: {}", lock_period.start_block);
             println!("  End Block: {}", lock_period.end_block);
         },
-        Ok(None) => {
+        None => {
             println!("❌ No current lock period found.");
         },
-        Err(e) => {
-            eprintln!("🚨 Error querying current lock period: {}", e);
-            return Err(e.into());
-        }
     }
 
     Ok(())
 }
 
-async fn handle_get_min_lock_amount() -> Result<(), Box<dyn std::error::Error>> {
-    println!("💰 Fetching Minimum Lock Amount...");
+async fn handle_get_min_lock_amount(opts: &GlobalOpts) -> Result<(), CliError> {
+    log::info!("💰 Fetching Minimum Lock Amount...");
 
-    let (api, _) = setup_substrate_client().await?;
+    let api = connect_any(opts).await?;
 
     // Build a dynamic storage query for MinLockAmount
     let storage_query = subxt::dynamic::storage("Credits", "MinLockAmount", vec![]);
 
     // Fetch the minimum lock amount
-    let min_lock_amount_result = api.storage().at_latest().await?.fetch(&storage_query).await;
+    let min_lock_amount_result = api.storage().at_latest().await?.fetch(&storage_query).await?;
 
     match min_lock_amount_result {
-        Ok(Some(min_lock_amount_value)) => {
+        Some(min_lock_amount_value) => {
             // Attempt to decode the minimum lock amount
             let min_lock_amount: u128 = min_lock_amount_value.as_type()?;
 
             println!("✅ Minimum Lock Amount:");
             println!("  Amount: {}", min_lock_amount);
         },
-        Ok(None) => {
+        None => {
             println!("❌ No minimum lock amount found.");
         },
-        Err(e) => {
-            eprintln!("🚨 Error querying minimum lock amount: {}", e);
-            return Err(e.into());
-        }
     }
 
     Ok(())
 }
 
-async fn handle_get_node_id() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔍 Fetching Local Peer ID...");
+async fn handle_get_node_id(opts: &GlobalOpts) -> Result<(), CliError> {
+    log::info!("🔍 Fetching Local Peer ID...");
 
-    let client = reqwest::Client::new();
-    let response = client
-        .post("https://rpc.hippius.network")
+    let node_id = detect_node_id(opts).await?;
+    println!("✅ Local Peer ID: {}", node_id);
+
+    Ok(())
+}
+
+/// Asks the local node (`system_localPeerId`) for its libp2p peer ID.
+async fn detect_node_id(opts: &GlobalOpts) -> Result<String, CliError> {
+    let rpc_url = local_node_rpc_url(opts);
+    let response = reqwest::Client::new()
+        .post(&rpc_url)
         .header("Content-Type", "application/json")
         .body(r#"{"id":1, "jsonrpc":"2.0", "method":"system_localPeerId"}"#)
         .send()
+        .await
+        .map_err(|e| CliError::RpcConnect(format!("could not reach the node at {}: {}", rpc_url, e)))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch Local Peer ID. Status: {}", response.status()).into());
+    }
+    let json: serde_json::Value = response.json().await?;
+    json["result"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("no peer ID in the response from {}", rpc_url).into())
+}
+
+/// Prints the local IPFS peer ID, asking the IPFS HTTP API and falling back
+/// to the repo config (`$IPFS_PATH/config`, default `~/.ipfs`) when the API is unreachable.
+async fn handle_get_ipfs_node_id(ipfs_api: Option<String>) -> Result<(), CliError> {
+    log::info!("🔍 Fetching IPFS Node ID...");
+
+    let ipfs_node_id = detect_ipfs_node_id(ipfs_api).await?;
+    println!("✅ IPFS Node ID: {}", ipfs_node_id);
+    Ok(())
+}
+
+/// The local IPFS peer ID, from `ipfs_api` (default: $IPFS_API_URL, else
+/// `http://127.0.0.1:5001`) or, if that is unreachable, the IPFS repo config.
+async fn detect_ipfs_node_id(ipfs_api: Option<String>) -> Result<String, CliError> {
+    let api_url = ipfs_api
+        .or_else(|| env::var("IPFS_API_URL").ok())
+        .unwrap_or_else(|| "http://127.0.0.1:5001".to_string());
+
+    match ipfs_id_from_api(&api_url).await {
+        Ok(id) => Ok(id),
+        Err(e) => {
+            log::warn!("⚠️ IPFS API at {} unavailable ({}), reading the IPFS config instead", api_url, e);
+            ipfs_id_from_config()
+        }
+    }
+}
+
+/// Asks the IPFS HTTP API (`/api/v0/id`) for the node's peer ID.
+async fn ipfs_id_from_api(api_url: &str) -> Result<String, CliError> {
+    let url = format!("{}/api/v0/id", api_url.trim_end_matches('/'));
+    let response: serde_json::Value = reqwest::Client::new()
+        .post(&url)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
         .await?;
 
-    if response.status().is_success() {
-        let json: serde_json::Value = response.json().await?;
-        if let Some(result) = json.get("result") {
-            println!("✅ Local Peer ID: {}", result);
-        } else {
-            println!("❌ No result found in the response.");
+    response["ID"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "IPFS API response has no ID field".into())
+}
+
+/// Reads `Identity.PeerID` from the IPFS repo config.
+fn ipfs_id_from_config() -> Result<String, CliError> {
+    let repo = match env::var("IPFS_PATH") {
+        Ok(path) => std::path::PathBuf::from(path),
+        Err(_) => home_dir().ok_or("Could not find home directory")?.join(".ipfs"),
+    };
+    let config_path = repo.join("config");
+    let contents = fs::read_to_string(&config_path)
+        .map_err(|e| format!("could not read {}: {}", config_path.display(), e))?;
+    let config: serde_json::Value = serde_json::from_str(&contents)?;
+
+    config["Identity"]["PeerID"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("{} has no Identity.PeerID", config_path.display()).into())
+}
+
+/// Template written by `doctor --fix` when no `.env` file exists
+const ENV_TEMPLATE: &str = "# Hippius CLI configuration
+SUBSTRATE_NODE_URL=wss://rpc.hippius.network
+# SUBSTRATE_SEED_PHRASE=\"<your coldkey mnemonic>\"
+";
+
+/// Diagnose the local setup and, with `fix`, offer to repair what can be repaired
+/// without touching the chain.
+async fn handle_doctor(opts: &GlobalOpts, fix: bool) -> Result<(), CliError> {
+    println!("🩺 Checking your Hippius setup...");
+    let mut problems = 0;
+
+    // Node keystore and hotkey directories
+    let keystore_path = opts.keystore_path();
+    let hotkeys_dir = get_hotkeys_dir();
+    for (label, dir) in [("Node keystore", keystore_path.as_str()), ("Hotkey keystore", hotkeys_dir.as_str())] {
+        if Path::new(dir).is_dir() {
+            println!("✅ {} directory exists: {}", label, dir);
+            continue;
+        }
+        problems += 1;
+        println!("❌ {} directory is missing: {}", label, dir);
+        if fix && confirm(opts, &format!("Create {}?", dir))? {
+            fs::create_dir_all(dir)?;
+            println!("🔧 Created {}", dir);
+            problems -= 1;
+        }
+    }
+
+    // Configuration
+    if opts.seed_file.is_some() {
+        println!("✅ Signer read from --seed-file");
+    } else if env::var("SUBSTRATE_SEED_PHRASE").is_ok() {
+        println!("✅ SUBSTRATE_SEED_PHRASE is set");
+    } else if opts.profile_settings.signer.is_some() {
+        println!("✅ Signer taken from profile '{}'", opts.profile.as_deref().unwrap_or_default());
+    } else {
+        problems += 1;
+        println!("❌ No signer configured; commands that submit transactions will fail");
+        if fix && !Path::new(".env").exists() && confirm(opts, "Write a template .env file in the current directory?")? {
+            fs::write(".env", ENV_TEMPLATE)?;
+            println!("🔧 Wrote template .env; fill in SUBSTRATE_SEED_PHRASE before signing anything");
+        }
+    }
+
+    // Coldkey (HIPS key)
+    let coldkey = if Path::new(&keystore_path).is_dir() { find_hips_key(&keystore_path)? } else { None };
+    if let Some(coldkey) = coldkey {
+        println!("✅ HIPS key (coldkey) found: {}", coldkey);
+    } else {
+        problems += 1;
+        println!("❌ No HIPS key (coldkey) found in {}", keystore_path);
+        if fix && Path::new(&keystore_path).is_dir() && confirm(opts, "Generate a new coldkey?")? {
+            let key_path = write_hips_key(&keystore_path)?;
+            println!("🔧 Wrote coldkey to {}", key_path);
+            problems -= 1;
+        }
+    }
+
+    // RPC endpoints (read-only connection check)
+    for url in rpc_endpoints(opts) {
+        match connect_with_timeout(opts, &url).await {
+            Ok(_) => println!("✅ RPC endpoint reachable: {}", url),
+            Err(e) => {
+                problems += 1;
+                println!("❌ RPC endpoint unreachable: {}", e);
+            }
         }
+    }
+
+    if problems == 0 {
+        println!("🎉 No problems found!");
     } else {
-        println!("❌ Failed to fetch Local Peer ID. Status: {}", response.status());
+        println!("⚠️ {} problem(s) remaining{}", problems, if fix { "" } else { "; rerun with --fix to repair what can be fixed locally" });
     }
 
     Ok(())
 }
 
-async fn handle_get_ipfs_node_id() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔍 Fetching IPFS Node ID...");
+/// Generates a coldkey mnemonic and stores it in the node keystore under the
+/// `hips` key type, printing the mnemonic once so it can be backed up.
+fn write_hips_key(keystore_path: &str) -> Result<String, CliError> {
+    let mnemonic = generate_mnemonic(12);
+    let pair = sr25519::Pair::from_string(&mnemonic, None)
+        .map_err(|e| format!("Failed to create pair: {:?}", e))?;
 
-    // Execute the shell command
-    let output = Command::new("bash")
-        .arg("-c")
-        .arg("cat /zfs/ipfs/data/config | grep -o '\"PeerID\": \"[^\"]*\"' | cut -d'\"' -f4")
-        .output()
-        .expect("Failed to execute command");
+    // Substrate keystore layout: hex(key type) ++ hex(public key), holding the phrase as a JSON string
+    let file_name = format!("{}{}", hex::encode("hips"), hex::encode(pair.public().0));
+    let key_path = Path::new(keystore_path).join(file_name);
+    write_secret_file(&key_path, serde_json::to_string(&mnemonic)?.as_bytes())?;
 
-    if output.status.success() {
-        let ipfs_node_id = String::from_utf8_lossy(&output.stdout);
-        println!("✅ IPFS Node ID: {}", ipfs_node_id.trim());
-    } else {
-        let error_message = String::from_utf8_lossy(&output.stderr);
-        println!("❌ Failed to fetch IPFS Node ID. Error: {}", error_message);
-    }
+    println!("📍 Coldkey Address: {}", pair.public().to_ss58check());
+    println!("📝 Mnemonic: {}", mnemonic);
+    println!("⚠️ WARNING: Store this mnemonic safely! It is the only way to recover this coldkey.");
+
+    Ok(key_path.display().to_string())
+}
 
+/// Writes a file holding secrets so that only the owner can read it. On Unix
+/// the file is created as 0600, and an existing file is narrowed to 0600
+/// before anything is written to it.
+fn write_secret_file(path: &Path, contents: &[u8]) -> Result<(), CliError> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents)?;
     Ok(())
 }
 
-async fn handle_get_hips_key() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔍 Checking for HIPS key files...");
+/// Asks a yes/no question on stdin; anything other than "y"/"yes" means no.
+/// `--yes` answers yes without asking, and a non-interactive stdin always means no.
+fn confirm(opts: &GlobalOpts, prompt: &str) -> Result<bool, CliError> {
+    if opts.yes {
+        return Ok(true);
+    }
+    if !std::io::stdin().is_terminal() {
+        eprintln!("❓ {} Not confirmed: stdin is not a terminal (pass --yes to proceed)", prompt);
+        return Ok(false);
+    }
+    print!("❓ {} [y/N] ", prompt);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+async fn handle_get_hips_key(opts: &GlobalOpts) -> Result<(), CliError> {
+    log::info!("🔍 Checking for HIPS key files...");
 
     // Call the check_keystore_files function
-    check_keystore_files(KEYSTORE_PATH)?;
+    check_keystore_files(&opts.keystore_path())?;
 
     Ok(())
 }
 
-fn check_keystore_files(keystore_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn check_keystore_files(keystore_path: &str) -> Result<(), CliError> {
     // Define the target prefix as a string (ASCII representation)
     let target_prefix = "68697073"; // This is the ASCII string "68697073"
 
     // Iterate over files in the keystore directory
-    let dir_entries = fs::read_dir(keystore_path)?;
+    let dir_entries = read_keystore_dir(keystore_path)?;
     let mut found = false;
 
     for entry in dir_entries {
@@ -2086,55 +7009,56 @@ fn check_keystore_files(keystore_path: &str) -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
-async fn handle_swap_node_owner(node_id: String, new_owner: String, signer_account: String) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔄 Swapping owner for node ID: {}", node_id);
+async fn handle_swap_node_owner(opts: &GlobalOpts, node_id: String, new_owner: &str) -> Result<(), CliError> {
+    let new_owner_account_id = resolve_account(opts, new_owner)?;
+    log::info!("🔄 Swapping owner for node ID: {}", node_id);
 
-    let api = setup_substrate_client().await?.0;
+    let (api, signer) = setup_substrate_client(opts).await?;
 
-    // Convert node_id and new_owner to the required types
+    // Convert node_id to the required type
     let node_id_bytes = node_id.clone().into_bytes();
-    let new_owner_account_id: AccountId32 = new_owner.parse().map_err(|_| "Invalid account ID")?;
-
-    // Define the path to the hotkey
-    let hotkeys_dir = get_hotkeys_dir();
-    let hotkey_path = format!("{}/{}", hotkeys_dir, signer_account);
-
-    // Check if the hotkey exists
-    let signer = if Path::new(&hotkey_path).exists() {
-        // Load the hotkey mnemonic from the keystore
-        let mnemonic = fs::read_to_string(&hotkey_path)?;
-        let mnemonic = Mnemonic::parse_in_normalized(Language::English, mnemonic.trim())?;
-        let seed = mnemonic.to_seed("");
-        let seed_array: [u8; 32] = seed[..32].try_into().map_err(|_| "Seed slice has incorrect length")?;
-        let hotkey_pair = sr25519::Pair::from_seed(&seed_array);
-        
-        // Create a PairSigner from the hotkey pair
-        PairSigner::new(hotkey_pair)
-    } else {
-        // Fall back to the default signer
-        let signer = setup_substrate_client().await?.1; // Assuming this returns the default signer
-        signer
-    };
 
     // Create the transaction to swap the node owner
     let tx = custom_runtime::tx()
         .registration()
         .swap_node_owner(node_id_bytes, new_owner_account_id);
 
-    let progress = api
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, &signer)
-        .await?;
-
-    println!("⏳ Waiting for transaction to be finalized...");
-    let _ = progress.wait_for_finalized_success().await?;
+    if submit_and_wait(opts, &api, &tx, &signer).await?.is_none() {
+        return Ok(());
+    }
 
-    println!("✅ Successfully swapped node owner for node ID: {}", node_id);
+    log::info!("✅ Successfully swapped node owner for node ID: {}", node_id);
     Ok(())
 }
 
-async fn handle_upload_to_ipfs(path: String) -> Result<(), Box<dyn Error>> {
-    println!("📦 Uploading to IPFS: {}", path);
+/// Adds a single file through an IPFS HTTP API (`/api/v0/add`), streaming it
+/// from disk rather than buffering it, and returns its CID.
+async fn ipfs_add_file(api_url: &str, path: &Path) -> Result<String, CliError> {
+    let file = File::open(path).await.map_err(|e| format!("could not open {}: {}", path.display(), e))?;
+    let length = file.metadata().await?.len();
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+    let body = reqwest::Body::wrap_stream(FramedRead::new(file, BytesCodec::new()));
+    let part = multipart::Part::stream_with_length(body, length).file_name(file_name);
+    let form = multipart::Form::new().part("file", part);
+
+    let url = format!("{}/api/v0/add", api_url.trim_end_matches('/'));
+    let response = reqwest::Client::new().post(&url).multipart(form).send().await?;
+    if !response.status().is_success() {
+        return Err(format!("IPFS add failed ({}): {}", response.status(), response.text().await?).into());
+    }
+
+    // The API answers with one JSON object per added entry; the last one is the file itself
+    let text = response.text().await?;
+    let added: serde_json::Value = serde_json::from_str(text.lines().last().unwrap_or_default())?;
+    added["Hash"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("IPFS add response has no Hash: {}", text).into())
+}
+
+async fn handle_upload_to_ipfs(path: String) -> Result<(), CliError> {
+    log::info!("📦 Uploading to IPFS: {}", path);
 
     let ipfs_api_url = "https://relay-fr.hippius.network/api/v0/add?recursive=true&wrap-with-directory=true";
     let client = reqwest::Client::new();
@@ -2181,8 +7105,363 @@ async fn handle_upload_to_ipfs(path: String) -> Result<(), Box<dyn Error>> {
         println!("✅ Uploaded to IPFS: {}", response_text);
     } else {
         let error_message = response.text().await?;
-        println!("❌ Upload failed. Error: {}", error_message);
+        return Err(format!("upload failed: {}", error_message).into());
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn os_name_decodes_from_blake2_128_concat_key() {
+        let encoded = b"ubuntu-22.04".to_vec().encode();
+        let mut key = sp_core::hashing::twox_128(b"Marketplace").to_vec();
+        key.extend(sp_core::hashing::twox_128(b"OSDiskImageUrls"));
+        key.extend(sp_core::hashing::blake2_128(&encoded));
+        key.extend(&encoded);
+
+        assert_eq!(os_name_from_key(&key).unwrap(), b"ubuntu-22.04");
+        assert!(os_name_from_key(&key[..40]).is_err());
+    }
+
+    #[test]
+    fn os_name_key_decoding_handles_any_name_length() {
+        let key_for = |name: &[u8]| {
+            let encoded = name.to_vec().encode();
+            let mut key = sp_core::hashing::twox_128(b"Marketplace").to_vec();
+            key.extend(sp_core::hashing::twox_128(b"OSDiskImageUrls"));
+            key.extend(sp_core::hashing::blake2_128(&encoded));
+            key.extend(encoded);
+            key
+        };
+
+        // Slicing the last 32 bytes used to return hash and length bytes for short
+        // names and cut long ones; both must now round-trip
+        for name in [&b"arch"[..], b"debian-12-bookworm-generic-amd64-cloud-image"] {
+            assert_eq!(os_name_from_key(&key_for(name)).unwrap(), name);
+        }
+
+        // A key whose hash does not cover the encoded name is rejected
+        let mut key = key_for(b"arch");
+        key[40] ^= 0xff;
+        assert!(os_name_from_key(&key).is_err());
+    }
+
+    #[test]
+    fn account_info_decodes_system_account_layout() {
+        // nonce 5, one consumer and provider, 10^12 free, 250 reserved, 100 frozen, new-logic flag set
+        let blob = hex::decode(
+            "05000000010000000100000000000000\
+             0010a5d4e80000000000000000000000\
+             fa000000000000000000000000000000\
+             64000000000000000000000000000000\
+             00000000000000000000000000000080",
+        )
+        .unwrap();
+
+        let info = AccountInfo::decode(&mut &blob[..]).unwrap();
+
+        assert_eq!(info.nonce, 5);
+        assert_eq!(info.providers, 1);
+        assert_eq!(info.data.free, 1_000_000_000_000);
+        assert_eq!(info.data.reserved, 250);
+        assert_eq!(info.data.frozen, 100);
+        assert_eq!(info.data.flags, 1 << 127);
+    }
+
+    #[test]
+    fn write_keypair_files_produces_ss58_and_seed() {
+        let dir = env::temp_dir().join(format!("hipc-generate-keys-{}", std::process::id()));
+
+        let KeypairFiles { address, public_key, mnemonic, seed, public_key_path, mnemonic_path, seed_path } =
+            write_keypair_files(&dir, KeyScheme::Sr25519, 12).unwrap();
+
+        let ss58 = fs::read_to_string(&public_key_path).unwrap();
+        assert_eq!(ss58, address);
+        assert_eq!(sr25519::Public::from_ss58check(&ss58).unwrap().0.to_vec(), public_key);
+        assert_eq!(sr25519::Pair::from_seed(&seed).public().0.to_vec(), public_key);
+
+        // The mnemonic recovers the key the way any Substrate wallet would
+        assert_eq!(fs::read_to_string(&mnemonic_path).unwrap(), mnemonic);
+        assert_eq!(mnemonic.split_whitespace().count(), 12);
+        assert_eq!(sr25519::Pair::from_phrase(&mnemonic, None).unwrap().1, seed);
+
+        // Signing with either file as --seed-file gives the same account
+        let opts = Cli::parse_from(["hipc", "whoami"]).opts;
+        let from_mnemonic = signer_pair(&opts, &SignerSpec::SeedFile(mnemonic_path.display().to_string())).unwrap();
+        let from_seed = signer_pair(&opts, &SignerSpec::SeedFile(seed_path.display().to_string())).unwrap();
+        assert_eq!(from_mnemonic.public(), from_seed.public());
+        assert_eq!(from_mnemonic.public().0.to_vec(), public_key);
+
+        let stored_seed = fs::read(&seed_path).unwrap();
+        assert_eq!(stored_seed.len(), 32);
+        assert_eq!(stored_seed, seed);
+
+        // A second run must not replace the first key's files
+        assert!(write_keypair_files(&dir, KeyScheme::Sr25519, 12).is_err());
+        assert_eq!(fs::read_to_string(&mnemonic_path).unwrap(), mnemonic);
+        assert_eq!(fs::read(&seed_path).unwrap(), seed);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&mnemonic_path).unwrap().permissions().mode() & 0o777, 0o600);
+            assert_eq!(fs::metadata(&seed_path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_keypair_files_addresses_ecdsa_keys_by_account_id() {
+        let dir = env::temp_dir().join(format!("hipc-generate-ecdsa-keys-{}", std::process::id()));
+
+        let KeypairFiles { address, public_key, seed, .. } = write_keypair_files(&dir, KeyScheme::Ecdsa, 24).unwrap();
+
+        let pair = ecdsa::Pair::from_seed(&seed);
+        assert_eq!(pair.public().0.to_vec(), public_key);
+        let account = sp_core::crypto::AccountId32::from_ss58check(&address).unwrap();
+        assert_eq!(<[u8; 32]>::from(account), sp_core::blake2_256(&public_key));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn generate_mnemonic_supports_every_bip39_length() {
+        for word_count in MNEMONIC_WORD_COUNTS {
+            let mnemonic = generate_mnemonic(word_count);
+            assert_eq!(mnemonic.split_whitespace().count(), word_count);
+            assert!(Mnemonic::parse_in_normalized(Language::English, &mnemonic).is_ok());
+            assert_eq!(parse_word_count(&word_count.to_string()), Ok(word_count));
+        }
+
+        assert!(parse_word_count("13").is_err());
+        assert!(parse_word_count("twelve").is_err());
+    }
+
+    #[test]
+    fn batch_ops_accept_numeric_and_string_amounts() {
+        let ops: Vec<BatchOp> = serde_json::from_str(r#"[
+            {"op": "transfer", "to": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", "amount": 1000},
+            {"op": "stake", "amount": "1000000000000000000000"},
+            {"op": "unpin", "cid": "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"}
+        ]"#).unwrap();
+
+        assert!(matches!(ops[0], BatchOp::Transfer { amount: 1000, .. }));
+        assert!(matches!(ops[1], BatchOp::Stake { amount: 1_000_000_000_000_000_000_000 }));
+        assert_eq!(ops.iter().map(BatchOp::pallet).collect::<Vec<_>>(), ["Balances", "Staking", "Marketplace"]);
+
+        assert!(serde_json::from_str::<Vec<BatchOp>>(r#"[{"op": "stake", "amount": "lots"}]"#).is_err());
+        assert!(serde_json::from_str::<Vec<BatchOp>>(r#"[{"op": "fly"}]"#).is_err());
+    }
+
+    #[test]
+    fn encrypted_mnemonic_round_trips_only_with_the_right_passphrase() {
+        let mnemonic = generate_mnemonic(12);
+        let encrypted = encrypt_mnemonic(&mnemonic, "correct horse").unwrap();
+
+        assert_eq!(decrypt_mnemonic(&encrypted, "correct horse").unwrap(), mnemonic);
+        assert!(decrypt_mnemonic(&encrypted, "wrong").is_err());
+    }
+
+    #[test]
+    fn utc_timestamps_format_as_calendar_dates() {
+        assert_eq!(format_utc_millis(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_utc_millis(951_782_400_000), "2000-02-29 00:00:00 UTC");
+        assert_eq!(format_utc_millis(1_700_000_000_999), "2023-11-14 22:13:20 UTC");
+    }
+
+    #[test]
+    fn decode_call_detects_bare_calls_and_signed_extrinsics() {
+        use subxt::ext::subxt_core::client::{ClientState, RuntimeVersion};
+
+        let metadata = subxt::Metadata::decode(&mut &include_bytes!("../metadata.scale")[..]).unwrap();
+        let dest = AccountId32::from([7u8; 32]);
+        let call = custom_runtime::tx().balances().transfer_keep_alive(MultiAddress::Id(dest.clone()), 42);
+
+        let call_data = subxt::ext::subxt_core::tx::create_unsigned::<PolkadotConfig, _>(&call, &metadata).unwrap();
+        let bare = &call_data.encoded()[2..]; // length prefix and version byte
+        let decoded = decode_call_or_extrinsic(&metadata, bare).unwrap();
+        assert_eq!(decoded.kind, "call");
+        assert!(decoded.call.starts_with("Balances.transfer_keep_alive"), "{}", decoded.call);
+        assert!(decoded.call.contains("42"), "{}", decoded.call);
+        assert!(decoded.call.contains(&dest.to_string()), "accounts print as SS58: {}", decoded.call);
+
+        let state = ClientState::<PolkadotConfig> {
+            genesis_hash: H256::zero(),
+            runtime_version: RuntimeVersion { spec_version: 1, transaction_version: 1 },
+            metadata: metadata.clone(),
+        };
+        let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+        let params = subxt::config::DefaultExtrinsicParamsBuilder::<PolkadotConfig>::new().nonce(3).build();
+        let signed = subxt::ext::subxt_core::tx::create_signed(&call, &state, &PairSigner::new(pair.clone()), params).unwrap();
+        let decoded = decode_call_or_extrinsic(&metadata, signed.encoded()).unwrap();
+        assert_eq!(decoded.kind, "signed extrinsic");
+        assert_eq!(decoded.signer, Some(AccountId32::from(pair.public().0).to_string()));
+        assert_eq!(decoded.call, describe_call(&metadata, bare).unwrap());
+
+        assert!(decode_call_or_extrinsic(&metadata, &[bare, &[0]].concat()).is_err());
+    }
+
+    #[test]
+    fn peer_ids_are_checked_before_registration() {
+        assert!(validate_peer_id("--node-id", "12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp").is_ok());
+        assert!(validate_peer_id("--node-id", "QmYyQSo1c1Ym7orWxLYvCrM2EmxFTANf8wXmmE7DWjhx5N").is_ok());
+        assert!(validate_peer_id("--node-id", "bafzaajaiaejcatfvvp3k26p36wv3zsx4yju5qxgsmupnjoefwwdj6ja25xyklorj").is_ok());
+        for bad in ["", "my-node", "12D3KooW0OIl", &"1".repeat(MAX_NODE_ID_LEN + 1)] {
+            assert!(matches!(validate_peer_id("--node-id", bad), Err(CliError::Invalid(_))), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn miners_cannot_register_without_an_ipfs_node_id() {
+        assert!(require_ipfs_node_id(CliNodeType::Validator, None).is_ok());
+        assert!(require_ipfs_node_id(CliNodeType::StorageMiner, Some("12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp")).is_ok());
+        assert!(matches!(require_ipfs_node_id(CliNodeType::StorageMiner, None), Err(CliError::Invalid(_))));
+        assert!(matches!(require_ipfs_node_id(CliNodeType::ComputeMiner, None), Err(CliError::Invalid(_))));
+    }
+
+    #[test]
+    fn signer_specs_parse_and_resolve_inline_mnemonics() {
+        assert_eq!(parse_signer_spec("coldkey"), Ok(SignerSpec::EnvColdkey));
+        assert_eq!(parse_signer_spec("hotkey:miner-1"), Ok(SignerSpec::Hotkey("miner-1".to_string())));
+        assert_eq!(parse_signer_spec("seed-file:/tmp/seed.bin"), Ok(SignerSpec::SeedFile("/tmp/seed.bin".to_string())));
+        assert_eq!(parse_signer_spec("mnemonic://Alice"), Ok(SignerSpec::Mnemonic("//Alice".to_string())));
+        for bad in ["", "hotkey", "hotkey:", "coldkey:x", "miner-1", "mnemonic: "] {
+            assert!(parse_signer_spec(bad).is_err(), "{:?}", bad);
+        }
+        let alice = signer_pair(&Cli::parse_from(["hipc", "whoami"]).opts, &SignerSpec::Mnemonic("//Alice".to_string())).unwrap();
+        assert_eq!(alice.public().to_ss58check(), "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY");
+    }
+
+    #[test]
+    fn verify_hotkeys_reports_empty_corrupt_and_misnamed_files() {
+        let dir = env::temp_dir().join(format!("hipc-verify-keystore-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mnemonic = generate_mnemonic(12);
+        let address = hotkey_pair_from_mnemonic(&mnemonic).unwrap().public().to_ss58check();
+        let other = hotkey_pair_from_mnemonic(&generate_mnemonic(12)).unwrap().public().to_ss58check();
+
+        let named = HotkeyFile { name: "good".to_string(), address: address.clone(), mnemonic: Some(mnemonic.clone()), encrypted_mnemonic: None };
+        fs::write(dir.join("good.json"), serde_json::to_string(&named).unwrap()).unwrap();
+        fs::write(dir.join(&address), &mnemonic).unwrap();
+        fs::write(dir.join(format!("{}.json", address)), r#"{"name":"legacy"}"#).unwrap();
+        fs::write(dir.join(&other), &mnemonic).unwrap();
+        fs::write(dir.join("empty.json"), "").unwrap();
+        fs::write(dir.join("junk.json"), "{").unwrap();
+
+        let checks = verify_hotkeys(dir.to_str().unwrap()).unwrap();
+        let problem = |file: &str| checks.iter().find(|check| check.file == file).unwrap().problem.clone();
+        assert_eq!(checks.len(), 5, "the legacy sidecar is not checked on its own");
+        assert_eq!(problem("good.json"), None);
+        assert_eq!(problem(&address), None);
+        assert!(problem(&other).unwrap().contains("derives"));
+        assert_eq!(problem("empty.json").as_deref(), Some("file is empty"));
+        assert!(problem("junk.json").unwrap().starts_with("not a hotkey file"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn miner_ids_accept_commas_and_repeated_flags() {
+        let cli = Cli::parse_from(["hipc", "storage", "pin", "QmYyQSo1c1Ym7orWxLYvCrM2EmxFTANf8wXmmE7DWjhx5N", "notes.txt", "--miner-ids", "miner1,miner2", "--miner-ids", "miner3"]);
+        let Commands::Storage { storage_command: StorageCommands::Pin { miner_ids, .. } } = cli.command else {
+            panic!("parsed as another command");
+        };
+        assert_eq!(
+            miner_id_bytes(miner_ids.as_deref()),
+            Some(vec![b"miner1".to_vec(), b"miner2".to_vec(), b"miner3".to_vec()])
+        );
+    }
+
+    #[test]
+    fn storage_cost_is_prorated_per_byte_and_rounded_up() {
+        assert_eq!(storage_cost(1 << 30, 1_000), 1_000);
+        assert_eq!(storage_cost(1 << 29, 1_000), 500);
+        assert_eq!(storage_cost(1, 1_000), 1);
+        assert_eq!(storage_cost(0, 1_000), 0);
+    }
+
+    #[test]
+    fn node_info_block_lists_every_field() {
+        let output = NodeInfoOutput {
+            node_id: "storage-node-01".to_string(),
+            node_type: "StorageMiner".to_string(),
+            ipfs_node_id: None,
+            status: "Online".to_string(),
+            registered_at: 1234,
+            owner: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+        };
+        assert_eq!(
+            format_node_info(&output),
+            "✅ Node Information:\n\
+             ------------------------\n\
+             Node ID: storage-node-01\n\
+             Node Type: StorageMiner\n\
+             IPFS Node ID: None\n\
+             Status: Online\n\
+             Registered At: 1234\n\
+             Owner: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY\n\
+             ------------------------\n"
+        );
+    }
+
+    #[test]
+    fn csv_output_has_header_even_when_empty() {
+        let rows = vec![LockedCreditOutput {
+            id: 7,
+            amount_locked: 1_000,
+            created_at: 42,
+            is_fulfilled: false,
+            tx_hash: None,
+        }];
+        assert_eq!(
+            String::from_utf8(csv_bytes(&rows).unwrap()).unwrap(),
+            "id,amount_locked,created_at,is_fulfilled,tx_hash\n7,1000,42,false,\n"
+        );
+        assert_eq!(
+            String::from_utf8(csv_bytes::<LockedCreditOutput>(&[]).unwrap()).unwrap(),
+            "id,amount_locked,created_at,is_fulfilled,tx_hash\n"
+        );
+    }
+
+    #[test]
+    fn write_secret_file_is_owner_only() {
+        let dir = env::temp_dir().join(format!("hipc-secret-file-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let fresh = dir.join("fresh");
+        let existing = dir.join("existing");
+        fs::write(&existing, "old contents that are longer").unwrap();
+
+        write_secret_file(&fresh, b"secret").unwrap();
+        write_secret_file(&existing, b"secret").unwrap();
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "secret");
+
+        // Plaintext hotkeys from create-hotkey and import-hotkey go through the same path
+        let mnemonic = generate_mnemonic(12);
+        let address = hotkey_pair_from_mnemonic(&mnemonic).unwrap().public().to_ss58check();
+        let hotkey = std::path::PathBuf::from(write_hotkey_file(dir.to_str().unwrap(), Some("plain".to_string()), &address, &mnemonic, false).unwrap());
+        assert_eq!(read_hotkey_file(&hotkey).unwrap().mnemonic.as_deref(), Some(mnemonic.as_str()));
+        assert!(write_hotkey_file(dir.to_str().unwrap(), Some("plain".to_string()), &address, &mnemonic, false).is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&fresh).unwrap().permissions().mode() & 0o777, 0o600);
+            assert_eq!(fs::metadata(&existing).unwrap().permissions().mode() & 0o777, 0o600);
+            assert_eq!(fs::metadata(&hotkey).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn bytes_to_display_falls_back_to_hex_for_non_utf8() {
+        assert_eq!(bytes_to_display(b"storage-node-01"), "storage-node-01");
+        assert_eq!(bytes_to_display(&[]), "");
+        assert_eq!(bytes_to_display(&[0xff, 0xfe]), "0xfffe");
+    }
+}